@@ -0,0 +1,242 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use log_num::{
+    slice::{self, NarPolicy},
+    stats, L32,
+};
+use std::hint::black_box;
+
+const LEN: usize = 4096;
+
+/// Random values with the same magnitudes in both formats.
+///
+/// Exponents are kept within ±32 so no f32 operation overflows.
+fn data(seed: u64) -> (Vec<L32>, Vec<f32>) {
+    values(seed, 3, true)
+}
+
+/// Random values with a logarithm in `[-2^(8 - shift), 2^(8 - shift))`, negative ones only if
+/// `signed`.
+fn values(seed: u64, shift: u32, signed: bool) -> (Vec<L32>, Vec<f32>) {
+    let mut state = seed;
+    (0..LEN)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            let sign = if signed { state as u32 & 0x80000000 } else { 0 };
+            let exp = (state >> 32) as i32 >> shift;
+            let l = L32::from_bits(sign | (exp as u32 & 0x7FFFFFFF));
+
+            let magnitude = (exp as f32 / (1 << 23) as f32).exp2();
+            let f = if sign != 0 { -magnitude } else { magnitude };
+            (l, f)
+        })
+        .unzip()
+}
+
+fn binary(c: &mut Criterion, name: &str, l: impl Fn(L32, L32) -> L32, f: impl Fn(f32, f32) -> f32) {
+    let (la, fa) = data(0x9E3779B97F4A7C15);
+    let (lb, fb) = data(0xD1B54A32D192ED03);
+    let mut lout = vec![L32::ONE; LEN];
+    let mut fout = vec![0.0; LEN];
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(LEN as u64));
+    group.bench_function(BenchmarkId::new("L32", LEN), |bench| {
+        bench.iter(|| {
+            for ((out, &a), &b) in lout.iter_mut().zip(&la).zip(&lb) {
+                *out = l(a, b);
+            }
+            black_box(&mut lout);
+        })
+    });
+    group.bench_function(BenchmarkId::new("f32", LEN), |bench| {
+        bench.iter(|| {
+            for ((out, &a), &b) in fout.iter_mut().zip(&fa).zip(&fb) {
+                *out = f(a, b);
+            }
+            black_box(&mut fout);
+        })
+    });
+    group.finish();
+}
+
+fn mul(c: &mut Criterion) {
+    binary(c, "mul", |a, b| a * b, |a, b| a * b);
+}
+
+fn div(c: &mut Criterion) {
+    binary(c, "div", |a, b| a / b, |a, b| a / b);
+}
+
+fn midpoint(c: &mut Criterion) {
+    binary(c, "midpoint", |a, b| a.midpoint(b), |a, b| (a * b).sqrt());
+}
+
+fn unary(c: &mut Criterion, name: &str, l: impl Fn(L32) -> L32, f: impl Fn(f32) -> f32) {
+    let (la, fa) = data(0x9E3779B97F4A7C15);
+    let mut lout = vec![L32::ONE; LEN];
+    let mut fout = vec![0.0; LEN];
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(LEN as u64));
+    group.bench_function(BenchmarkId::new("L32", LEN), |bench| {
+        bench.iter(|| {
+            for (out, &a) in lout.iter_mut().zip(&la) {
+                *out = l(a);
+            }
+            black_box(&mut lout);
+        })
+    });
+    group.bench_function(BenchmarkId::new("f32", LEN), |bench| {
+        bench.iter(|| {
+            for (out, &a) in fout.iter_mut().zip(&fa) {
+                *out = f(a);
+            }
+            black_box(&mut fout);
+        })
+    });
+    group.finish();
+}
+
+fn sqrt(c: &mut Criterion) {
+    unary(c, "sqrt", |a| a.sqrt(), |a| a.sqrt());
+}
+
+fn mul_pow2(c: &mut Criterion) {
+    unary(c, "mul_pow2", |a| a.mul_pow2(5), |a| a * 32.0);
+}
+
+/// Benchmarks a reduction of `values`.
+fn reduce<L, F>(
+    c: &mut Criterion,
+    name: &str,
+    (la, fa): (Vec<L32>, Vec<f32>),
+    l: impl Fn(&[L32]) -> L,
+    f: impl Fn(&[f32]) -> F,
+) {
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(LEN as u64));
+    group.bench_function(BenchmarkId::new("L32", LEN), |bench| {
+        bench.iter(|| l(black_box(&la)))
+    });
+    group.bench_function(BenchmarkId::new("f32", LEN), |bench| {
+        bench.iter(|| f(black_box(&fa)))
+    });
+    group.finish();
+}
+
+fn min(c: &mut Criterion) {
+    reduce(
+        c,
+        "min",
+        data(0x9E3779B97F4A7C15),
+        |v| slice::min(v, NarPolicy::Skip),
+        |v| v.iter().copied().fold(f32::INFINITY, f32::min),
+    );
+}
+
+fn argmax(c: &mut Criterion) {
+    reduce(
+        c,
+        "argmax",
+        data(0x9E3779B97F4A7C15),
+        |v| slice::argmax(v, NarPolicy::Skip),
+        |v| {
+            v.iter()
+                .enumerate()
+                .filter(|(_, x)| !x.is_nan())
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(i, _)| i)
+        },
+    );
+}
+
+fn geometric_mean(c: &mut Criterion) {
+    // The mean of negative values is NaR, and NaN for f32.
+    let positive = values(0x9E3779B97F4A7C15, 3, false);
+    let f = |v: &[f32]| {
+        let sum: f32 = v.iter().map(|x| x.log2()).sum();
+        (sum / v.len() as f32).exp2()
+    };
+    assert!(f(&positive.1).is_finite());
+    reduce(c, "geometric_mean", positive, stats::geometric_mean, f);
+}
+
+fn cumprod(c: &mut Criterion) {
+    // Magnitudes in [2^(-1/16), 2^(1/16)), the running products stay far from the range of f32.
+    let (la, fa) = values(0x9E3779B97F4A7C15, 12, true);
+    let mut acc = 1.0f32;
+    assert!(fa.iter().all(|x| {
+        acc *= x;
+        acc.is_normal()
+    }));
+    let mut lout = la.clone();
+    let mut fout = fa.clone();
+
+    let mut group = c.benchmark_group("cumprod");
+    group.throughput(Throughput::Elements(LEN as u64));
+    group.bench_function(BenchmarkId::new("L32", LEN), |bench| {
+        bench.iter(|| {
+            lout.copy_from_slice(&la);
+            slice::cumprod(&mut lout);
+            black_box(&mut lout);
+        })
+    });
+    group.bench_function(BenchmarkId::new("f32", LEN), |bench| {
+        bench.iter(|| {
+            fout.copy_from_slice(&fa);
+            let mut acc = 1.0;
+            for x in fout.iter_mut() {
+                acc *= *x;
+                *x = acc;
+            }
+            black_box(&mut fout);
+        })
+    });
+    group.finish();
+}
+
+fn sort(c: &mut Criterion) {
+    let mut values = Vec::new();
+    for seed in 1..=16 {
+        values.extend(data(seed).0);
+    }
+    let mut scratch = values.clone();
+    let mut sorted = values.clone();
+
+    let mut group = c.benchmark_group("sort");
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function(BenchmarkId::new("radix", values.len()), |bench| {
+        bench.iter(|| {
+            sorted.copy_from_slice(&values);
+            slice::radix_sort(&mut sorted, &mut scratch);
+            black_box(&mut sorted);
+        })
+    });
+    group.bench_function(BenchmarkId::new("comparison", values.len()), |bench| {
+        bench.iter(|| {
+            sorted.copy_from_slice(&values);
+            sorted.sort_unstable_by_key(|v| v.to_sortable_bits());
+            black_box(&mut sorted);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    mul,
+    div,
+    midpoint,
+    sqrt,
+    mul_pow2,
+    min,
+    argmax,
+    geometric_mean,
+    cumprod,
+    sort
+);
+criterion_main!(benches);
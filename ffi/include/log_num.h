@@ -0,0 +1,44 @@
+#ifndef LOG_NUM_H
+#define LOG_NUM_H
+
+/* Generated with cbindgen, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Not a Real (NaR).
+ */
+#define L32_NAR 3221225472
+
+/**
+ * The value 0.0
+ */
+#define L32_ZERO 1073741824
+
+/**
+ * The value 1.0
+ */
+#define L32_ONE 0
+
+/**
+ * Multiplies two L32 values.
+ */
+uint32_t l32_mul(uint32_t a, uint32_t b);
+
+/**
+ * Divides two L32 values.
+ */
+uint32_t l32_div(uint32_t a, uint32_t b);
+
+/**
+ * Calculates the square root of an L32 value.
+ *
+ * Returns NaR if the input is negative or NaR.
+ */
+uint32_t l32_sqrt(uint32_t a);
+
+#endif  /* LOG_NUM_H */
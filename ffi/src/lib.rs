@@ -0,0 +1,45 @@
+//! C bindings for `log-num`.
+//!
+//! Values are passed as their raw `uint32_t` bits, see `L32::to_bits`. The header is generated
+//! with `cbindgen --config cbindgen.toml --output include/log_num.h`.
+
+use log_num::L32;
+
+/// Not a Real (NaR).
+pub const L32_NAR: u32 = 0xC0000000;
+/// The value 0.0
+pub const L32_ZERO: u32 = 0x40000000;
+/// The value 1.0
+pub const L32_ONE: u32 = 0;
+
+/// Multiplies two L32 values.
+#[no_mangle]
+pub extern "C" fn l32_mul(a: u32, b: u32) -> u32 {
+    (L32::from_bits(a) * L32::from_bits(b)).to_bits()
+}
+
+/// Divides two L32 values.
+#[no_mangle]
+pub extern "C" fn l32_div(a: u32, b: u32) -> u32 {
+    (L32::from_bits(a) / L32::from_bits(b)).to_bits()
+}
+
+/// Calculates the square root of an L32 value.
+///
+/// Returns NaR if the input is negative or NaR.
+#[no_mangle]
+pub extern "C" fn l32_sqrt(a: u32) -> u32 {
+    L32::from_bits(a).sqrt().to_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(L32_NAR, L32::NAR.to_bits());
+        assert_eq!(L32_ZERO, L32::ZERO.to_bits());
+        assert_eq!(L32_ONE, L32::ONE.to_bits());
+    }
+}
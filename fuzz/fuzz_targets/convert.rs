@@ -0,0 +1,30 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use log_num::L32;
+
+fuzz_target!(|input: (&str, u32)| {
+    let (s, bits) = input;
+
+    // Parsing must never panic, and the raw bits syntax gives back exactly the parsed bits.
+    if let Ok(x) = s.parse::<L32>() {
+        if s.starts_with("l:") {
+            let hex = format!("l:{:08X}", x.to_bits());
+            assert_eq!(hex.parse::<L32>(), Ok(x), "{s}");
+        }
+    }
+    let x = L32::from_bits(bits);
+    assert_eq!(format!("l:{bits:08X}").parse::<L32>(), Ok(x));
+    assert_eq!(format!("l:0x{bits:x}").parse::<L32>(), Ok(x));
+
+    // Exact f32 conversions are each other's inverse where they succeed.
+    let f = f32::from_bits(bits);
+    if let Ok(y) = L32::try_from(f) {
+        // -0.0 converts to ZERO, which converts back to 0.0.
+        let back = y.to_f32_exact().map(f32::to_bits);
+        assert_eq!(back, Some(if f == 0.0 { 0 } else { bits }), "{f:e}");
+    }
+    if let Some(f) = x.to_f32_exact() {
+        assert_eq!(L32::try_from(f), Ok(x), "{x:?}");
+    }
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use log_num::{context::MathContext, reference, L32};
+use log_num_fuzz::assert_close;
+
+fuzz_target!(|input: (u32, u32)| {
+    let a = L32::from_bits(input.0);
+    let b = L32::from_bits(input.1);
+    let mut ctx = MathContext::default();
+    let res = ctx.mul(a, b);
+    assert_eq!(res, b * a);
+
+    // The result wraps around on overflow, the reference gives NaR.
+    let flags = ctx.take_flags();
+    if !flags.overflow && !flags.underflow {
+        assert_close(res, reference::mul(a, b), 0);
+    }
+});
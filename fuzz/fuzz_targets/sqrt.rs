@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use log_num::{reference, L32};
+use log_num_fuzz::assert_close;
+
+fuzz_target!(|input: u32| {
+    let a = L32::from_bits(input);
+
+    // Halving the exponent gives ties, the f64 reference can land on either side of them.
+    assert_close(a.sqrt(), reference::sqrt(a), 1);
+});
@@ -0,0 +1,9 @@
+//! Shared checks for the fuzz targets, the oracle is [`log_num::reference`].
+
+use log_num::L32;
+
+/// Asserts that `res` is at most `max_steps` representable values away from `expected`.
+pub fn assert_close(res: L32, expected: L32, max_steps: u32) {
+    let diff = res.to_sortable_bits().abs_diff(expected.to_sortable_bits());
+    assert!(diff <= max_steps, "expected {expected:?}, got {res:?}");
+}
@@ -0,0 +1,23 @@
+//! Compute shader running the core `L32` operations, built by `log-num-gpu`.
+
+#![no_std]
+
+use log_num::L32;
+use spirv_std::{glam::UVec3, spirv};
+
+/// Writes `a * b`, `a / b`, `a.sqrt()` and `a.midpoint(b)` of every element to 4 consecutive
+/// elements of `out`.
+#[spirv(compute(threads(64)))]
+pub fn main(
+    #[spirv(global_invocation_id)] id: UVec3,
+    #[spirv(storage_buffer, descriptor_set = 0, binding = 0)] a: &[u32],
+    #[spirv(storage_buffer, descriptor_set = 0, binding = 1)] b: &[u32],
+    #[spirv(storage_buffer, descriptor_set = 0, binding = 2)] out: &mut [u32],
+) {
+    let i = id.x as usize;
+    let (x, y) = (L32::from_bits(a[i]), L32::from_bits(b[i]));
+    out[4 * i] = (x * y).to_bits();
+    out[4 * i + 1] = (x / y).to_bits();
+    out[4 * i + 2] = x.sqrt().to_bits();
+    out[4 * i + 3] = x.midpoint(y).to_bits();
+}
@@ -0,0 +1,44 @@
+//! Smoke test for using log-num in Rust compute shaders with
+//! [rust-gpu](https://github.com/Rust-GPU/rust-gpu).
+//!
+//! The `shader` crate uses the core `L32` operations from a compute entry point. The tests
+//! compile it to SPIR-V and check that the module is valid.
+
+use spirv_builder::{ModuleResult, SpirvBuilder};
+use std::path::PathBuf;
+
+/// Compiles the `shader` crate and returns the path of the SPIR-V module.
+pub fn build_shader() -> Result<PathBuf, spirv_builder::SpirvBuilderError> {
+    let shader = concat!(env!("CARGO_MANIFEST_DIR"), "/shader");
+    let res = SpirvBuilder::new(shader, "spirv-unknown-vulkan1.2").build()?;
+    match res.module {
+        ModuleResult::SingleModule(path) => Ok(path),
+        ModuleResult::MultiModule(_) => unreachable!("multimodule is not enabled"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use naga::{
+        front::spv,
+        valid::{Capabilities, ValidationFlags, Validator},
+        ShaderStage,
+    };
+
+    #[test]
+    fn shader() {
+        let spv = std::fs::read(build_shader().unwrap()).unwrap();
+        let module = spv::parse_u8_slice(&spv, &spv::Options::default()).unwrap();
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .unwrap();
+
+        let [entry] = &module.entry_points[..] else {
+            panic!("expected a single entry point");
+        };
+        assert_eq!(entry.name, "main");
+        assert_eq!(entry.stage, ShaderStage::Compute);
+        assert_eq!(entry.workgroup_size, [64, 1, 1]);
+    }
+}
@@ -0,0 +1,125 @@
+//! Python bindings for `log-num`.
+//!
+//! Build with `maturin develop` and run the tests with `pytest python/tests`.
+
+use log_num::L32;
+use pyo3::{buffer::PyBuffer, exceptions::PyValueError, prelude::*};
+
+/// A 32 bit logarithmic number.
+#[pyclass(name = "L32", module = "log_num", frozen, eq, hash, from_py_object)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PyL32(L32);
+
+#[pymethods]
+impl PyL32 {
+    /// Not a Real (NaR).
+    #[classattr]
+    const NAR: Self = Self(L32::NAR);
+    /// The value 0.0
+    #[classattr]
+    const ZERO: Self = Self(L32::ZERO);
+    /// The value 1.0
+    #[classattr]
+    const ONE: Self = Self(L32::ONE);
+
+    /// Raw transmutation from u32.
+    #[staticmethod]
+    fn from_bits(bits: u32) -> Self {
+        Self(L32::from_bits(bits))
+    }
+
+    /// Raw transmutation to u32.
+    fn to_bits(&self) -> u32 {
+        self.0.to_bits()
+    }
+
+    /// Calculates the square root.
+    ///
+    /// Returns NaR if the input is negative or NaR.
+    fn sqrt(&self) -> Self {
+        Self(self.0.sqrt())
+    }
+
+    fn __mul__(&self, rhs: &Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+
+    fn __truediv__(&self, rhs: &Self) -> Self {
+        Self(self.0 / rhs.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("L32.from_bits(0x{:08X})", self.0.to_bits())
+    }
+}
+
+/// Converts a sequence of raw bits to L32 values.
+#[pyfunction]
+fn from_bits_list(bits: Vec<u32>) -> Vec<PyL32> {
+    bits.into_iter().map(PyL32::from_bits).collect()
+}
+
+/// Converts a sequence of L32 values to their raw bits.
+#[pyfunction]
+fn to_bits_list(values: Vec<PyL32>) -> Vec<u32> {
+    values.iter().map(PyL32::to_bits).collect()
+}
+
+/// Applies `op` to every pair of elements of `a` and `b`, storing the raw bits in `out`.
+fn binary(
+    py: Python<'_>,
+    a: &PyBuffer<u32>,
+    b: &PyBuffer<u32>,
+    out: &PyBuffer<u32>,
+    op: impl Fn(L32, L32) -> L32,
+) -> PyResult<()> {
+    if a.item_count() != out.item_count() || b.item_count() != out.item_count() {
+        return Err(PyValueError::new_err("buffers have different lengths"));
+    }
+    let a = a.to_vec(py)?;
+    let b = b.to_vec(py)?;
+    let res: Vec<u32> = a
+        .iter()
+        .zip(&b)
+        .map(|(&a, &b)| op(L32::from_bits(a), L32::from_bits(b)).to_bits())
+        .collect();
+    out.copy_from_slice(py, &res)
+}
+
+/// Multiplies two buffers of raw bits element wise, storing the raw bits in `out`.
+///
+/// Accepts any object supporting the buffer protocol with 32 bit unsigned items, such as NumPy
+/// arrays with dtype `uint32` or `array.array("I")`. `out` may be one of the inputs.
+#[pyfunction]
+fn mul_bits(
+    py: Python<'_>,
+    a: PyBuffer<u32>,
+    b: PyBuffer<u32>,
+    out: PyBuffer<u32>,
+) -> PyResult<()> {
+    binary(py, &a, &b, &out, |a, b| a * b)
+}
+
+/// Divides two buffers of raw bits element wise, storing the raw bits in `out`.
+///
+/// See `mul_bits` for the accepted buffers.
+#[pyfunction]
+fn div_bits(
+    py: Python<'_>,
+    a: PyBuffer<u32>,
+    b: PyBuffer<u32>,
+    out: PyBuffer<u32>,
+) -> PyResult<()> {
+    binary(py, &a, &b, &out, |a, b| a / b)
+}
+
+#[pymodule]
+#[pyo3(name = "log_num")]
+fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyL32>()?;
+    m.add_function(wrap_pyfunction!(from_bits_list, m)?)?;
+    m.add_function(wrap_pyfunction!(to_bits_list, m)?)?;
+    m.add_function(wrap_pyfunction!(mul_bits, m)?)?;
+    m.add_function(wrap_pyfunction!(div_bits, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+from array import array
+
+import pytest
+
+from log_num import L32, div_bits, from_bits_list, mul_bits, to_bits_list
+
+
+def test_constants():
+    assert L32.NAR.to_bits() == 0xC0000000
+    assert L32.ZERO.to_bits() == 0x40000000
+    assert L32.ONE.to_bits() == 0
+
+
+def test_bits_round_trip():
+    for bits in [0, 1, 0x40000000, 0xC0000000, 0xDEADBEEF, 0xFFFFFFFF]:
+        assert L32.from_bits(bits).to_bits() == bits
+    assert to_bits_list(from_bits_list([1, 2, 3])) == [1, 2, 3]
+
+
+def test_operators():
+    two = L32.from_bits(0x00800000)
+    four = L32.from_bits(0x01000000)
+    assert two * two == four
+    assert four / two == two
+    assert four.sqrt() == two
+    assert L32.ZERO * L32.NAR == L32.NAR
+    assert two / L32.ZERO == L32.NAR
+
+
+def test_hash_and_repr():
+    assert hash(L32.from_bits(5)) == hash(L32.from_bits(5))
+    assert repr(L32.ONE) == "L32.from_bits(0x00000000)"
+
+
+def test_buffers():
+    a = array("I", [0x00800000, 0x40000000, 0xC0000000, 0x80000000])
+    b = array("I", [0x00800000, 0x00800000, 0x00000000, 0x00800000])
+    out = array("I", [0] * 4)
+    mul_bits(a, b, out)
+    assert list(out) == [0x01000000, 0x40000000, 0xC0000000, 0x80800000]
+    div_bits(out, b, out)
+    assert list(out) == list(a)
+
+    with pytest.raises(ValueError):
+        mul_bits(a, array("I", [0]), out)
@@ -0,0 +1,236 @@
+//! Explicit contexts carrying a rounding mode and exception flags.
+
+use crate::{
+    exception::{self, ExceptionKind},
+    L32,
+};
+
+/// Direction in which the logarithm of inexact results is rounded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// To nearest, ties to even. This is what the regular operations do.
+    #[default]
+    NearestEven,
+    /// Towards the smaller magnitude.
+    TowardZero,
+    /// Towards the larger magnitude.
+    AwayFromZero,
+}
+
+/// Exceptions raised by the operations in a context.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Flags {
+    pub overflow: bool,
+    pub underflow: bool,
+    pub nar: bool,
+    /// An operation's result had to be rounded.
+    pub inexact: bool,
+}
+
+/// Rounding mode and accumulated exception flags for a sequence of operations.
+///
+/// With the default rounding, operations give the same results as the regular ones. The flags
+/// are sticky: they stay raised until taken with [`take_flags`](Self::take_flags). Unlike the
+/// [`exception`] counters and handler, no global state is involved, so contexts on different
+/// threads or in different parts of a program don't affect each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MathContext {
+    pub rounding: Rounding,
+    pub flags: Flags,
+}
+
+impl MathContext {
+    /// Creates a context with the given rounding and no flags raised.
+    #[inline]
+    pub const fn new(rounding: Rounding) -> Self {
+        Self {
+            rounding,
+            flags: Flags {
+                overflow: false,
+                underflow: false,
+                nar: false,
+                inexact: false,
+            },
+        }
+    }
+
+    /// Returns the raised flags and clears them.
+    #[inline]
+    pub fn take_flags(&mut self) -> Flags {
+        core::mem::take(&mut self.flags)
+    }
+
+    /// Multiplies, see [`L32::mul`](core::ops::Mul::mul). The result is always exact.
+    #[inline]
+    pub fn mul(&mut self, a: L32, b: L32) -> L32 {
+        let res = a * b;
+        let exp = i64::from(a.raw_exp()) + i64::from(b.raw_exp());
+        self.record(&[a, b], Some(exp), false, res);
+        res
+    }
+
+    /// Divides, see [`L32::div`](core::ops::Div::div). The result is always exact.
+    #[inline]
+    pub fn div(&mut self, a: L32, b: L32) -> L32 {
+        let res = a / b;
+        let exp = i64::from(a.raw_exp()) - i64::from(b.raw_exp());
+        self.record(&[a, b], Some(exp), false, res);
+        res
+    }
+
+    /// Calculates the square root, see [`L32::sqrt`].
+    ///
+    /// The logarithm is halved and rounded with the context's rounding.
+    #[inline]
+    pub fn sqrt(&mut self, x: L32) -> L32 {
+        let exp = x.raw_exp();
+        let mut res = L32::from_bits(self.halve(exp) as u32 & 0x7FFFFFFF);
+
+        if x == L32::ZERO {
+            res = L32::ZERO;
+        }
+        if x.sign() {
+            res = L32::NAR;
+        }
+
+        exception::check("sqrt", &[x], None, exp & 1 != 0, res);
+        self.record(&[x], None, exp & 1 != 0, res);
+
+        res
+    }
+
+    /// Calculates the geometric midpoint, see [`L32::midpoint`].
+    ///
+    /// The logarithms are averaged and rounded with the context's rounding.
+    #[inline]
+    pub fn midpoint(&mut self, a: L32, b: L32) -> L32 {
+        // Both exponents are 31 bits wide, so the sum can't overflow.
+        let sum = a.raw_exp() + b.raw_exp();
+        let sign = a.to_bits() & 0x80000000;
+        let mut res = L32::from_bits(sign | (self.halve(sum) as u32 & 0x7FFFFFFF));
+
+        if a.sign() != b.sign() {
+            res = L32::NAR;
+        }
+        if a == L32::ZERO || b == L32::ZERO {
+            res = L32::ZERO;
+        }
+        if a == L32::NAR || b == L32::NAR {
+            res = L32::NAR;
+        }
+
+        exception::check("midpoint", &[a, b], None, sum & 1 != 0, res);
+        self.record(&[a, b], None, sum & 1 != 0, res);
+
+        res
+    }
+
+    /// Halves a fixed point exponent, rounding the lost bit.
+    #[inline]
+    fn halve(&self, exp: i32) -> i32 {
+        match self.rounding {
+            Rounding::NearestEven => (exp >> 1) + (exp & (exp >> 1) & 1),
+            Rounding::TowardZero => exp >> 1,
+            Rounding::AwayFromZero => (exp >> 1) + (exp & 1),
+        }
+    }
+
+    /// Raises the flags for the outcome of an operation, see [`exception::check`].
+    #[inline]
+    fn record(&mut self, operands: &[L32], exp: Option<i64>, inexact: bool, result: L32) {
+        match exception::classify(operands, exp, result) {
+            Some(ExceptionKind::Overflow) => self.flags.overflow = true,
+            Some(ExceptionKind::Underflow) => self.flags.underflow = true,
+            Some(ExceptionKind::Nar) => self.flags.nar = true,
+            None if inexact && result != L32::ZERO && result != L32::NAR => {
+                self.flags.inexact = true
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_bits;
+
+    const fn flags(overflow: bool, underflow: bool, nar: bool, inexact: bool) -> Flags {
+        Flags {
+            overflow,
+            underflow,
+            nar,
+            inexact,
+        }
+    }
+
+    #[test]
+    fn rounding() {
+        fn test(bits: u32, nearest: u32, toward_zero: u32, away_from_zero: u32) {
+            let x = L32::from_bits(bits);
+            let expected = [nearest, toward_zero, away_from_zero];
+            let modes = [
+                Rounding::NearestEven,
+                Rounding::TowardZero,
+                Rounding::AwayFromZero,
+            ];
+            for (rounding, expected) in modes.into_iter().zip(expected) {
+                let mut ctx = MathContext::new(rounding);
+                assert_eq!(ctx.sqrt(x), L32::from_bits(expected));
+                assert_eq!(ctx.midpoint(x, L32::ONE), L32::from_bits(expected));
+            }
+        }
+
+        test(0x00000000, 0x00000000, 0x00000000, 0x00000000);
+        test(0x00800000, 0x00400000, 0x00400000, 0x00400000);
+        test(0x00000001, 0x00000000, 0x00000000, 0x00000001);
+        test(0x00000003, 0x00000002, 0x00000001, 0x00000002);
+        test(0x7FFFFFFF, 0x00000000, 0x7FFFFFFF, 0x00000000);
+        test(0x7FFFFFFD, 0x7FFFFFFE, 0x7FFFFFFE, 0x7FFFFFFF);
+        test(0x3FFFFFFF, 0x20000000, 0x1FFFFFFF, 0x20000000);
+        test(0x40000001, 0x60000000, 0x60000000, 0x60000001);
+        test(0x40000000, 0x40000000, 0x40000000, 0x40000000);
+
+        let mut ctx = MathContext::default();
+        let mut state = 0x2545F4914F6CDD1Du64;
+        for _ in 0..10000 {
+            let a = L32::from_bits(random_bits(&mut state) & 0x7FFFFFFF);
+            let b = L32::from_bits(random_bits(&mut state) & 0x7FFFFFFF);
+            assert_eq!(ctx.sqrt(a), a.sqrt());
+            assert_eq!(ctx.midpoint(a, b), a.midpoint(b));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn flags_are_sticky() {
+        let mut ctx = MathContext::default();
+        let two = L32::from_bits(0x00800000);
+        assert_eq!(ctx.mul(two, two), L32::from_bits(0x01000000));
+        assert_eq!(ctx.sqrt(two), L32::from_bits(0x00400000));
+        assert_eq!(ctx.take_flags(), Flags::default());
+
+        ctx.sqrt(L32::from_bits(0x00000001));
+        assert_eq!(ctx.flags, flags(false, false, false, true));
+        ctx.mul(L32::from_bits(0x3FFFFFFF), two);
+        assert_eq!(ctx.flags, flags(true, false, false, true));
+        ctx.div(L32::from_bits(0x40000001), two);
+        assert_eq!(ctx.flags, flags(true, true, false, true));
+        ctx.div(two, L32::ZERO);
+        assert_eq!(ctx.take_flags(), flags(true, true, true, true));
+        assert_eq!(ctx.flags, Flags::default());
+
+        // NaR operands and exact results raise nothing.
+        ctx.mul(L32::NAR, two);
+        ctx.midpoint(L32::NAR, two);
+        ctx.sqrt(L32::NAR);
+        ctx.midpoint(two, L32::from_bits(0x01800000));
+        ctx.div(L32::ZERO, two);
+        assert_eq!(ctx.take_flags(), Flags::default());
+
+        ctx.midpoint(two, L32::from_bits(0x80800000));
+        assert_eq!(ctx.take_flags(), flags(false, false, true, false));
+        ctx.sqrt(L32::from_bits(0x80800000));
+        assert_eq!(ctx.take_flags(), flags(false, false, true, false));
+    }
+}
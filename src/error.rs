@@ -0,0 +1,72 @@
+//! Error types.
+
+use core::fmt;
+
+/// An error parsing an [`L32`](crate::L32).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseLnError {
+    /// The input is empty.
+    Empty,
+    /// The input doesn't match any supported syntax.
+    InvalidSyntax,
+    /// The magnitude is too large to represent.
+    Overflow,
+    /// The magnitude is too small to represent.
+    Underflow,
+    /// The value can't be represented exactly.
+    Inexact,
+}
+
+impl fmt::Display for ParseLnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "cannot parse L32 from empty string",
+            Self::InvalidSyntax => "invalid L32 literal",
+            Self::Overflow => "number too large to fit in L32",
+            Self::Underflow => "number too small to fit in L32",
+            Self::Inexact => "number not exactly representable in L32",
+        })
+    }
+}
+
+impl core::error::Error for ParseLnError {}
+
+/// An error converting a float to an [`L32`](crate::L32) without rounding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TryFromFloatError {
+    /// The input is NaN.
+    Nan,
+    /// The input is infinite.
+    Infinite,
+    /// The magnitude is too small to represent.
+    Underflow,
+    /// The value can't be represented exactly.
+    Inexact,
+}
+
+impl fmt::Display for TryFromFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Nan => "cannot convert NaN to L32",
+            Self::Infinite => "cannot convert infinity to L32",
+            Self::Underflow => "number too small to fit in L32",
+            Self::Inexact => "number not exactly representable in L32",
+        })
+    }
+}
+
+impl core::error::Error for TryFromFloatError {}
+
+/// An error for a value that is NaR where that isn't allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NarError;
+
+impl fmt::Display for NarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is NaR")
+    }
+}
+
+impl core::error::Error for NarError {}
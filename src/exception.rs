@@ -0,0 +1,292 @@
+//! Reporting of exceptional operations.
+//!
+//! With the `trace-exceptions` feature, a handler installed with [`set_handler`] is called
+//! whenever an operation overflows, underflows or produces NaR from operands that aren't NaR.
+//!
+//! With the `counters` feature, the number of such operations and of inexact operations is
+//! recorded in global counters, see [`counters`].
+
+use crate::L32;
+
+/// Kind of exceptional condition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExceptionKind {
+    /// The exponent of the exact result was too large to represent.
+    Overflow,
+    /// The exponent of the exact result was too small to represent.
+    Underflow,
+    /// The result is NaR while none of the operands are.
+    Nar,
+}
+
+/// An operation that raised an exception.
+#[derive(Clone, Copy, Debug)]
+pub struct Exception<'a> {
+    /// The condition that was raised.
+    pub kind: ExceptionKind,
+    /// Name of the operation, e.g. `"mul"`.
+    pub op: &'static str,
+    /// Inputs of the operation, in order.
+    pub operands: &'a [L32],
+    /// The value the operation returned.
+    pub result: L32,
+}
+
+/// Number of exceptional operations since the last [`reset_counters`].
+///
+/// Counters saturate instead of wrapping around.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Counters {
+    pub overflow: u32,
+    pub underflow: u32,
+    pub nar: u32,
+    /// Operations whose result had to be rounded.
+    pub inexact: u32,
+}
+
+/// Returns the counts of exceptional operations on all threads.
+#[cfg(feature = "counters")]
+pub fn counters() -> Counters {
+    use core::sync::atomic::Ordering::Relaxed;
+
+    Counters {
+        overflow: counters::OVERFLOW.load(Relaxed),
+        underflow: counters::UNDERFLOW.load(Relaxed),
+        nar: counters::NAR.load(Relaxed),
+        inexact: counters::INEXACT.load(Relaxed),
+    }
+}
+
+/// Sets all counters to 0.
+#[cfg(feature = "counters")]
+pub fn reset_counters() {
+    use core::sync::atomic::Ordering::Relaxed;
+
+    counters::OVERFLOW.store(0, Relaxed);
+    counters::UNDERFLOW.store(0, Relaxed);
+    counters::NAR.store(0, Relaxed);
+    counters::INEXACT.store(0, Relaxed);
+}
+
+#[cfg(feature = "counters")]
+mod counters {
+    use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+    pub(super) static OVERFLOW: AtomicU32 = AtomicU32::new(0);
+    pub(super) static UNDERFLOW: AtomicU32 = AtomicU32::new(0);
+    pub(super) static NAR: AtomicU32 = AtomicU32::new(0);
+    pub(super) static INEXACT: AtomicU32 = AtomicU32::new(0);
+
+    pub(super) fn increment(counter: &AtomicU32) {
+        let _ = counter.try_update(Relaxed, Relaxed, |n| n.checked_add(1));
+    }
+}
+
+/// Installs a function that is called for every exception, replacing the previous one.
+///
+/// The handler is global and may be called from multiple threads at once.
+#[cfg(feature = "trace-exceptions")]
+pub fn set_handler(handler: fn(&Exception)) {
+    handler::HANDLER.store(handler as *mut (), core::sync::atomic::Ordering::Release);
+}
+
+/// Removes the handler installed with [`set_handler`].
+#[cfg(feature = "trace-exceptions")]
+pub fn clear_handler() {
+    handler::HANDLER.store(core::ptr::null_mut(), core::sync::atomic::Ordering::Release);
+}
+
+#[cfg(feature = "trace-exceptions")]
+mod handler {
+    use super::Exception;
+    use core::sync::atomic::{AtomicPtr, Ordering};
+
+    pub(super) static HANDLER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+    pub(super) fn call(exception: &Exception) {
+        let handler = HANDLER.load(Ordering::Acquire);
+        if !handler.is_null() {
+            // SAFETY: Only `fn(&Exception)` pointers are stored in `HANDLER`.
+            let handler = unsafe { core::mem::transmute::<*mut (), fn(&Exception)>(handler) };
+            handler(exception);
+        }
+    }
+}
+
+/// Determines the exception raised by an operation, if any.
+///
+/// The arguments are the same as for [`check`].
+#[inline]
+pub(crate) fn classify(operands: &[L32], exp: Option<i64>, result: L32) -> Option<ExceptionKind> {
+    let special = operands.iter().any(|&x| x == L32::ZERO || x == L32::NAR);
+    // An overflowing exponent can wrap around to NaR, report the cause.
+    match exp {
+        Some(exp) if !special && exp >= 0x40000000 => Some(ExceptionKind::Overflow),
+        Some(exp) if !special && exp <= -0x40000000 => Some(ExceptionKind::Underflow),
+        _ if result == L32::NAR && !operands.contains(&L32::NAR) => Some(ExceptionKind::Nar),
+        _ => None,
+    }
+}
+
+/// Reports any exception raised by an operation.
+///
+/// `exp` is the exponent of the exact result as a fixed point number, for operations where it
+/// can be out of range. It's only checked when no operand is ZERO or NaR. `inexact` is whether
+/// the result had to be rounded.
+#[cfg(any(
+    feature = "trace-exceptions",
+    feature = "counters",
+    feature = "panic-on-nar",
+))]
+#[inline]
+pub(crate) fn check(
+    op: &'static str,
+    operands: &[L32],
+    exp: Option<i64>,
+    inexact: bool,
+    result: L32,
+) {
+    let nar = result == L32::NAR && !operands.contains(&L32::NAR);
+
+    #[cfg(any(feature = "trace-exceptions", feature = "counters"))]
+    {
+        let kind = classify(operands, exp, result);
+
+        #[cfg(feature = "counters")]
+        match kind {
+            Some(ExceptionKind::Overflow) => counters::increment(&counters::OVERFLOW),
+            Some(ExceptionKind::Underflow) => counters::increment(&counters::UNDERFLOW),
+            Some(ExceptionKind::Nar) => counters::increment(&counters::NAR),
+            None if inexact && result != L32::ZERO && result != L32::NAR => {
+                counters::increment(&counters::INEXACT)
+            }
+            None => {}
+        }
+
+        #[cfg(feature = "trace-exceptions")]
+        if let Some(kind) = kind {
+            handler::call(&Exception {
+                kind,
+                op,
+                operands,
+                result,
+            });
+        }
+    }
+    #[cfg(not(any(feature = "trace-exceptions", feature = "counters")))]
+    let _ = exp;
+    #[cfg(not(feature = "counters"))]
+    let _ = inexact;
+
+    if cfg!(feature = "panic-on-nar") && nar {
+        panic!("{op} produced NaR from {operands:?}");
+    }
+}
+
+#[cfg(not(any(
+    feature = "trace-exceptions",
+    feature = "counters",
+    feature = "panic-on-nar",
+)))]
+#[inline(always)]
+pub(crate) fn check(
+    _op: &'static str,
+    _operands: &[L32],
+    _exp: Option<i64>,
+    _inexact: bool,
+    _result: L32,
+) {
+}
+
+#[cfg(all(test, any(feature = "counters", feature = "trace-exceptions")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "counters")]
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn counters() {
+        // The counters are shared with the other tests running in parallel, so only check that
+        // they increase.
+        let before = super::counters();
+        let _ = L32::from_bits(0x3FFFFFFF) * L32::from_bits(0x00000001);
+        let _ = L32::from_bits(0x40000001) / L32::from_bits(0x00000001);
+        let _ = L32::ONE / L32::ZERO;
+        let _ = L32::from_bits(0x00000001).sqrt();
+        let after = super::counters();
+        assert!(after.overflow > before.overflow);
+        assert!(after.underflow > before.underflow);
+        assert!(after.nar > before.nar);
+        assert!(after.inexact > before.inexact);
+    }
+
+    #[cfg(feature = "trace-exceptions")]
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn trace_exceptions() {
+        extern crate std;
+
+        use std::{cell::RefCell, thread_local, vec::Vec};
+        use ExceptionKind::*;
+
+        type Record = (ExceptionKind, &'static str, Vec<u32>, u32);
+
+        // Other tests running in parallel call the handler too.
+        thread_local! {
+            static RECORDED: RefCell<Vec<Record>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn record(e: &Exception) {
+            let operands = e.operands.iter().map(|x| x.to_bits()).collect();
+            RECORDED.with_borrow_mut(|r| r.push((e.kind, e.op, operands, e.result.to_bits())));
+        }
+
+        fn take() -> Vec<Record> {
+            RECORDED.take()
+        }
+
+        set_handler(record);
+
+        let _ = L32::from_bits(0x3FFFFFFF) * L32::from_bits(0x00000001);
+        assert_eq!(
+            take(),
+            [(Overflow, "mul", [0x3FFFFFFF, 1].into(), 0x40000000)]
+        );
+        let _ = L32::from_bits(0xBFFFFFFF) * L32::from_bits(0x00000001);
+        assert_eq!(
+            take(),
+            [(Overflow, "mul", [0xBFFFFFFF, 1].into(), 0xC0000000)]
+        );
+        let _ = L32::from_bits(0x40000001) / L32::from_bits(0x00000001);
+        assert_eq!(
+            take(),
+            [(Underflow, "div", [0x40000001, 1].into(), 0x40000000)]
+        );
+        let _ = L32::from_bits(0x40000001).mul_pow2(-1);
+        assert_eq!(
+            take(),
+            [(Underflow, "mul_pow2", [0x40000001].into(), 0x3F800001)]
+        );
+        let _ = L32::ONE >> i32::MIN;
+        assert_eq!(take(), [(Overflow, "mul_pow2", [0].into(), 0)]);
+        let _ = L32::ONE << i32::MIN;
+        assert_eq!(take(), [(Underflow, "mul_pow2", [0].into(), 0)]);
+        let _ = L32::ONE / L32::ZERO;
+        assert_eq!(take(), [(Nar, "div", [0, 0x40000000].into(), 0xC0000000)]);
+        let _ = L32::from_bits(0x80000000).sqrt();
+        assert_eq!(take(), [(Nar, "sqrt", [0x80000000].into(), 0xC0000000)]);
+
+        // NaR operands, ZERO operands and in range results are not exceptional.
+        let _ = L32::NAR * L32::from_bits(0x3FFFFFFF);
+        let _ = L32::ZERO * L32::from_bits(0x3FFFFFFF);
+        let _ = L32::ZERO / L32::from_bits(0x00000001);
+        let _ = L32::from_bits(0x3FFFFFFE) * L32::from_bits(0x00000001);
+        let _ = L32::from_bits(0x40000002) / L32::from_bits(0x00000001);
+        let _ = L32::NAR.sqrt();
+        assert_eq!(take(), []);
+
+        clear_handler();
+        let _ = L32::ONE / L32::ZERO;
+        assert_eq!(take(), []);
+    }
+}
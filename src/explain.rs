@@ -0,0 +1,142 @@
+//! Introspection of the bit layout, for debugging and teaching.
+
+use crate::L32;
+use core::fmt;
+
+/// Class of a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Class {
+    Zero,
+    Nar,
+    /// Any other value, these all have a sign and a magnitude.
+    Finite,
+}
+
+/// The fields of a value, see [`L32::explain`].
+///
+/// `Display` prints a one line summary, e.g.
+/// `0x00C00000: +2^(1 + 4194304/2^23) ≈ 2.8284271247461907`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Explanation {
+    pub bits: u32,
+    pub class: Class,
+    /// See [`L32::sign`].
+    pub sign: bool,
+    /// See [`L32::exponent`].
+    pub exponent: i8,
+    /// See [`L32::fraction`].
+    pub fraction: u32,
+    /// The real value, accurate to about 15 significant digits. 0.0 for ZERO and NaN for NaR.
+    pub approx: f64,
+}
+
+impl L32 {
+    /// Breaks the value down into its fields.
+    pub fn explain(self) -> Explanation {
+        let class = if self == Self::ZERO {
+            Class::Zero
+        } else if self == Self::NAR {
+            Class::Nar
+        } else {
+            Class::Finite
+        };
+
+        Explanation {
+            bits: self.to_bits(),
+            class,
+            sign: self.sign(),
+            exponent: self.exponent(),
+            fraction: self.fraction(),
+            approx: approx(self),
+        }
+    }
+}
+
+/// Approximates the real value with f64 arithmetic only.
+fn approx(x: L32) -> f64 {
+    if x == L32::ZERO {
+        return 0.0;
+    }
+    if x == L32::NAR {
+        return f64::NAN;
+    }
+
+    // 2^f = e^(f ln 2), the Taylor series converges quickly since f ln 2 < 0.7.
+    let t = f64::from(x.fraction()) / f64::from(1 << L32::FRACTION_BITS) * core::f64::consts::LN_2;
+    let (mut term, mut sum) = (1.0, 1.0);
+    for k in 1..=20 {
+        term *= t / f64::from(k);
+        sum += term;
+    }
+    // The exponent is in range of the f64 exponent, so this is an exact power of two.
+    let scale = f64::from_bits(((i64::from(x.exponent()) + 1023) as u64) << 52);
+
+    let magnitude = sum * scale;
+    if x.sign() {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010X}: ", self.bits)?;
+        match self.class {
+            Class::Zero => f.write_str("ZERO"),
+            Class::Nar => f.write_str("NaR"),
+            Class::Finite => {
+                let sign = if self.sign { '-' } else { '+' };
+                write!(
+                    f,
+                    "{sign}2^({} + {}/2^{}) ≈ {}",
+                    self.exponent,
+                    self.fraction,
+                    L32::FRACTION_BITS,
+                    self.approx
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_bits;
+
+    #[test]
+    fn explain() {
+        extern crate std;
+        use std::string::ToString;
+
+        let x = L32::from_bits(0x80C00000).explain();
+        assert_eq!(
+            (x.class, x.sign, x.exponent, x.fraction),
+            (Class::Finite, true, 1, 0x400000)
+        );
+        assert!((x.approx + 2f64.powf(1.5)).abs() < 1e-15);
+        assert_eq!(
+            x.to_string(),
+            "0x80C00000: -2^(1 + 4194304/2^23) ≈ -2.8284271247461907"
+        );
+
+        assert_eq!(L32::ZERO.explain().class, Class::Zero);
+        assert_eq!(L32::ZERO.explain().approx, 0.0);
+        assert_eq!(L32::ZERO.explain().to_string(), "0x40000000: ZERO");
+        assert!(L32::NAR.explain().approx.is_nan());
+        assert_eq!(L32::NAR.explain().to_string(), "0xC0000000: NaR");
+        assert_eq!(L32::ONE.explain().approx, 1.0);
+
+        let mut state = 0x2545F4914F6CDD1Du64;
+        for _ in 0..1000 {
+            let x = L32::from_bits(random_bits(&mut state));
+            if x == L32::ZERO || x == L32::NAR {
+                continue;
+            }
+            let exact = 2f64.powf(f64::from(x.raw_exp()) / f64::from(1 << 23));
+            let exact = if x.sign() { -exact } else { exact };
+            assert!((x.explain().approx / exact - 1.0).abs() < 1e-14, "{x:?}");
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Bit exact golden model of the operations on plain integers.
+//!
+//! These are the algorithms [`L32`](crate::L32) itself uses, written against `u32` bit patterns
+//! with nothing but integer operations so they can be transliterated to an HDL or C. See the
+//! documentation of `L32` for the encoding. In short: bit 31 is the sign and bits 30..0 hold the
+//! base 2 logarithm of the magnitude as two's complement fixed point with 23 fraction bits. The
+//! most negative logarithm encodes ZERO (`0x40000000`) and, with the sign set, NaR
+//! (`0xC0000000`).
+
+const SIGN: u32 = 0x80000000;
+const EXP: u32 = 0x7FFFFFFF;
+const ZERO: u32 = 0x40000000;
+const NAR: u32 = 0xC0000000;
+
+/// Sign extends the 31 bit logarithm.
+#[inline]
+const fn exp(a: u32) -> i32 {
+    (a << 1) as i32 >> 1
+}
+
+/// Multiplication: the logarithms are added, wrapping around on overflow.
+#[inline]
+pub const fn l32_mul_bits(a: u32, b: u32) -> u32 {
+    let sign = (a ^ b) & SIGN;
+    let exp = a.wrapping_add(b) & EXP;
+    let mut res = sign | exp;
+
+    if a == ZERO || b == ZERO {
+        res = ZERO;
+    }
+    if a == NAR || b == NAR {
+        res = NAR;
+    }
+
+    res
+}
+
+/// Division: the logarithms are subtracted, wrapping around on overflow.
+#[inline]
+pub const fn l32_div_bits(a: u32, b: u32) -> u32 {
+    let sign = (a ^ b) & SIGN;
+    let exp = a.wrapping_sub(b) & EXP;
+    let mut res = sign | exp;
+
+    if a == ZERO {
+        res = ZERO;
+    }
+    if a == NAR || b == NAR || b == ZERO {
+        res = NAR;
+    }
+
+    res
+}
+
+/// Square root: the logarithm is halved and rounded to nearest, ties to even.
+#[inline]
+pub const fn l32_sqrt_bits(a: u32) -> u32 {
+    // The sign bit doesn't matter, if it's set the result is NaR anyway.
+    let exp = exp(a);
+    let exp = (exp >> 1) + (exp & (exp >> 1) & 1);
+    let mut res = exp as u32 & EXP;
+
+    if a == ZERO {
+        res = ZERO;
+    }
+    if a & SIGN != 0 {
+        res = NAR;
+    }
+
+    res
+}
+
+/// Geometric midpoint: the logarithms are averaged and rounded to nearest, ties to even.
+#[inline]
+pub const fn l32_midpoint_bits(a: u32, b: u32) -> u32 {
+    // Both logarithms are 31 bits wide, so the sum can't overflow.
+    let sum = exp(a) + exp(b);
+    let exp = (sum >> 1) + (sum & (sum >> 1) & 1);
+    let mut res = (a & SIGN) | (exp as u32 & EXP);
+
+    if (a ^ b) & SIGN != 0 {
+        res = NAR;
+    }
+    if a == ZERO || b == ZERO {
+        res = ZERO;
+    }
+    if a == NAR || b == NAR {
+        res = NAR;
+    }
+
+    res
+}
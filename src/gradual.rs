@@ -0,0 +1,423 @@
+//! Experimental logarithmic numbers with gradual underflow.
+
+use crate::L32;
+use core::{fmt, ops::*};
+
+/// An experimental 32 bit logarithmic number with gradual underflow.
+///
+/// The encoding is that of [`L32`], except for the smallest integer exponent. Instead of
+/// logarithms in `[-128, -127)` with 23 fraction bits, its 2^23 bit patterns hold a subnormal
+/// analog: 23 binades from 2^-128 down to 2^-150, losing one fraction bit per binade. Tiny
+/// values like long products of probabilities lose precision gradually instead of wrapping
+/// around or flushing to ZERO at 2^-128. ZERO and NaR have the same bits as in L32.
+///
+/// Like with L32, products and quotients of values above 2^-127 are exact if they are too. Results
+/// in the subnormal range round to nearest, ties to even, and round to ZERO below 2^-150.5.
+/// Results above the range saturate to the largest magnitude. No exceptions are reported, the
+/// `panic-on-nar`, `trace-exceptions` and `counters` features only cover L32.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct G32(u32);
+
+/// Fraction bits of the fixed point logarithms used for computation.
+const FRAC_BITS: u32 = 32;
+/// Integer part of the logarithm of the smallest magnitude.
+const MIN_EXP: i64 = -150;
+/// Integer part of the logarithm of the smallest magnitude with full precision.
+const MIN_NORMAL_EXP: i64 = -127;
+
+impl G32 {
+    /// Not a Real (NaR).
+    ///
+    /// Exceptional value for operations where the result cannot be expressed as a real number.
+    pub const NAR: Self = Self(0xC0000000);
+
+    /// The value 0.0
+    pub const ZERO: Self = Self(0x40000000);
+    /// The value 1.0
+    pub const ONE: Self = Self(0);
+
+    /// Raw transmutation to u32.
+    #[inline]
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Raw transmutation from u32.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns `true` for the values below 2^-127, which have reduced precision.
+    ///
+    /// ZERO and NaR are not subnormal.
+    #[inline]
+    pub const fn is_subnormal(self) -> bool {
+        self.0 & 0x7F800000 == 0x40000000 && self.0 & 0x7FFFFF != 0
+    }
+
+    /// Calculates the square root.
+    ///
+    /// Returns NaR if the input is negative or NaR.
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        let mut res = Self::from_log(0, self.log() >> 1);
+
+        if self == Self::ZERO {
+            res = Self::ZERO;
+        }
+        if self.0 & 0x80000000 != 0 {
+            res = Self::NAR;
+        }
+
+        res
+    }
+
+    /// Decodes the logarithm of the magnitude as a fixed point number.
+    ///
+    /// The result is meaningless for ZERO and NaR.
+    #[inline]
+    fn log(self) -> i64 {
+        if !self.is_subnormal() {
+            let exp = (self.0 << 1) as i32 >> 1;
+            return i64::from(exp) << (FRAC_BITS - L32::FRACTION_BITS);
+        }
+
+        // The position of the leading one selects the binade, the bits below it are the fraction.
+        let code = self.0 & 0x7FFFFF;
+        let frac_bits = 31 - code.leading_zeros();
+        let frac = i64::from(code - (1 << frac_bits));
+        ((MIN_EXP + i64::from(frac_bits)) << FRAC_BITS) + (frac << (FRAC_BITS - frac_bits))
+    }
+
+    /// Encodes a magnitude from its logarithm as a fixed point number.
+    ///
+    /// Rounds to nearest, ties to even, saturates to the largest magnitude and rounds to ZERO
+    /// below half of the smallest magnitude.
+    #[inline]
+    fn from_log(sign: u32, log: i64) -> Self {
+        let int = log >> FRAC_BITS;
+        // The shift and offset that turn the logarithm into the 31 bits below the sign. Below
+        // 2^-127 the 23 low bits of these hold the offset of the subnormal binade, followed by
+        // its fraction.
+        let (shift, bits) = if int >= MIN_NORMAL_EXP {
+            (FRAC_BITS - L32::FRACTION_BITS, log)
+        } else if int >= MIN_EXP {
+            let frac_bits = (int - MIN_EXP) as u32;
+            let frac = log - (int << FRAC_BITS);
+            let min_normal = MIN_NORMAL_EXP << L32::FRACTION_BITS;
+            // The leading one of the binade, shifted up to the same position as the fraction.
+            let lead = min_normal - (1 << L32::FRACTION_BITS) + (1 << frac_bits);
+            let shift = FRAC_BITS - frac_bits;
+            (shift, (lead << shift) + frac)
+        } else {
+            // Half of the smallest magnitude is the midpoint between it and ZERO.
+            let lead = (MIN_NORMAL_EXP - 1) << L32::FRACTION_BITS;
+            let half_exp = (MIN_EXP << FRAC_BITS) - (1 << (FRAC_BITS - 1));
+            let res = if log > half_exp { lead + 1 } else { lead };
+            (0, res)
+        };
+
+        let mut exp = bits >> shift;
+        if shift > 0 {
+            let rem = bits & ((1 << shift) - 1);
+            let half = 1 << (shift - 1);
+            if rem > half || (rem == half && exp & 1 != 0) {
+                exp += 1;
+            }
+        }
+        let exp = exp.min(0x3FFFFFFF);
+
+        let mut res = Self(sign | (exp as u32 & 0x7FFFFFFF));
+        if res.0 & 0x7FFFFFFF == Self::ZERO.0 {
+            res = Self::ZERO;
+        }
+
+        res
+    }
+}
+
+impl From<L32> for G32 {
+    /// Rounds to the nearest G32, which only differs for L32 values below 2^-127.
+    #[inline]
+    fn from(x: L32) -> Self {
+        let sign = x.to_bits() & 0x80000000;
+        let log = i64::from(x.raw_exp()) << (FRAC_BITS - L32::FRACTION_BITS);
+        let mut res = Self::from_log(sign, log);
+
+        if x == L32::ZERO {
+            res = Self::ZERO;
+        }
+        if x == L32::NAR {
+            res = Self::NAR;
+        }
+
+        res
+    }
+}
+
+impl From<G32> for L32 {
+    /// Converts exactly above 2^-128, saturating to the smallest magnitude of L32 otherwise.
+    #[inline]
+    fn from(x: G32) -> Self {
+        let shift = FRAC_BITS - L32::FRACTION_BITS;
+        let exp = (x.log() >> shift).max(-0x3FFFFFFF);
+        let mut res = L32::from_bits((x.0 & 0x80000000) | (exp as u32 & 0x7FFFFFFF));
+
+        if x == G32::ZERO {
+            res = L32::ZERO;
+        }
+        if x == G32::NAR {
+            res = L32::NAR;
+        }
+
+        res
+    }
+}
+
+impl fmt::Debug for G32 {
+    /// Prints the bits and the decoded logarithm, e.g. `0x40400000: +2^(-128 + 0/2^32)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010X}: ", self.0)?;
+        if *self == Self::ZERO {
+            return f.write_str("ZERO");
+        }
+        if *self == Self::NAR {
+            return f.write_str("NaR");
+        }
+
+        let sign = if self.0 & 0x80000000 != 0 { '-' } else { '+' };
+        let log = self.log();
+        write!(
+            f,
+            "{sign}2^({} + {}/2^{FRAC_BITS})",
+            log >> FRAC_BITS,
+            log as u32
+        )
+    }
+}
+
+impl Mul<G32> for G32 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: G32) -> Self {
+        let sign = (self.0 ^ rhs.0) & 0x80000000;
+        let mut res = Self::from_log(sign, self.log() + rhs.log());
+
+        if self == Self::ZERO || rhs == Self::ZERO {
+            res = Self::ZERO;
+        }
+        if self == Self::NAR || rhs == Self::NAR {
+            res = Self::NAR;
+        }
+
+        res
+    }
+}
+
+impl MulAssign<G32> for G32 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: G32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<G32> for G32 {
+    type Output = G32;
+
+    #[inline]
+    fn div(self, rhs: G32) -> Self::Output {
+        let sign = (self.0 ^ rhs.0) & 0x80000000;
+        let mut res = Self::from_log(sign, self.log() - rhs.log());
+
+        if self == Self::ZERO {
+            res = Self::ZERO;
+        }
+        if self == Self::NAR || rhs == Self::NAR || rhs == Self::ZERO {
+            res = Self::NAR;
+        }
+
+        res
+    }
+}
+
+impl DivAssign<G32> for G32 {
+    #[inline]
+    fn div_assign(&mut self, rhs: G32) {
+        *self = *self / rhs;
+    }
+}
+
+impl Default for G32 {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_bits;
+
+    #[test]
+    fn encoding() {
+        fn test(bits: u32, log: i64) {
+            assert_eq!(G32(bits).log(), log);
+            assert_eq!(G32::from_log(bits & 0x80000000, log), G32(bits));
+        }
+
+        // 1, 2, 1/2, -4
+        test(0x00000000, 0);
+        test(0x00800000, 1 << 32);
+        test(0x7F800000, -1 << 32);
+        test(0x81000000, 2 << 32);
+        // Largest magnitude and smallest normal magnitude.
+        test(0x3FFFFFFF, (128 << 32) - (1 << 9));
+        test(0x40800000, -127 << 32);
+        test(0x40800001, (-127 << 32) + (1 << 9));
+        // Subnormal binades, one fraction bit less each.
+        test(0x407FFFFF, (-127 << 32) - (1 << 10));
+        test(0x40400000, -128 << 32);
+        test(0x40200000, -129 << 32);
+        test(0x40300000, (-129 << 32) + (1 << 31));
+        test(0x40000003, (-149 << 32) + (1 << 31));
+        test(0x40000002, -149 << 32);
+        test(0x40000001, -150 << 32);
+        test(0xC0000001, -150 << 32);
+    }
+
+    #[test]
+    fn rounding() {
+        fn test(log: i64, bits: u32) {
+            assert_eq!(G32::from_log(0, log), G32(bits));
+        }
+
+        // Normal values round to 23 fraction bits.
+        test((1 << 8) - 1, 0x00000000);
+        test(1 << 8, 0x00000000);
+        test(3 << 8, 0x00000002);
+        // Subnormal values round to the fraction bits of their binade.
+        test((-128 << 32) + (1 << 9), 0x40400000);
+        test((-128 << 32) + (1 << 9) + 1, 0x40400001);
+        test((-128 << 32) - (1 << 10), 0x40400000);
+        test((-128 << 32) - (1 << 10) - 1, 0x403FFFFF);
+        test((-149 << 32) + (1 << 30), 0x40000002);
+        test((-149 << 32) + (3 << 30), 0x40000004);
+        // Rounding up can carry into the next binade.
+        test((-127 << 32) - (1 << 9), 0x40800000);
+        // Below the range
+        test(-150 << 32, 0x40000001);
+        test((-150 << 32) - (1 << 31) + 1, 0x40000001);
+        test((-150 << 32) - (1 << 31), 0x40000000);
+        test(-1 << 40, 0x40000000);
+        assert_eq!(G32::from_log(0x80000000, -1 << 40), G32::ZERO);
+        // Saturation
+        test(128 << 32, 0x3FFFFFFF);
+        test(1 << 40, 0x3FFFFFFF);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        for _ in 0..10000 {
+            let x = G32(random_bits(&mut state));
+            if x != G32::ZERO && x != G32::NAR {
+                assert_eq!(G32::from_log(x.0 & 0x80000000, x.log()), x);
+            }
+            if x != G32::NAR && x.log() > -128 << 32 {
+                assert_eq!(G32::from(L32::from(x)), x);
+            }
+
+            // L32 values of at least 2^-127 have the same bits.
+            let l = L32::from_bits(random_bits(&mut state));
+            if l.exponent() != -128 {
+                assert_eq!(G32::from(l).0, l.to_bits());
+                assert_eq!(L32::from(G32::from(l)), l);
+            }
+        }
+
+        assert_eq!(G32::from(L32::ZERO), G32::ZERO);
+        assert_eq!(G32::from(L32::NAR), G32::NAR);
+        assert_eq!(G32::from(L32::ONE), G32::ONE);
+        assert_eq!(L32::from(G32::ZERO), L32::ZERO);
+        assert_eq!(L32::from(G32::NAR), L32::NAR);
+        // The smallest L32 binade rounds to one fraction bit less.
+        assert_eq!(G32::from(L32::from_bits(0x40000001)), G32(0x40400000));
+        assert_eq!(G32::from(L32::from_bits(0x40000003)), G32(0x40400002));
+        assert_eq!(G32::from(L32::from_bits(0xC07FFFFF)), G32(0xC0800000));
+        // Subnormals down to 2^-128 saturate.
+        assert_eq!(L32::from(G32(0x40400001)), L32::from_bits(0x40000002));
+        assert_eq!(L32::from(G32(0x40400000)), L32::from_bits(0x40000001));
+        assert_eq!(L32::from(G32(0x40000001)), L32::from_bits(0x40000001));
+        assert_eq!(L32::from(G32(0xC0000001)), L32::from_bits(0xC0000001));
+    }
+
+    #[test]
+    fn debug() {
+        extern crate std;
+        use std::format;
+
+        assert_eq!(format!("{:?}", G32::ONE), "0x00000000: +2^(0 + 0/2^32)");
+        assert_eq!(
+            format!("{:?}", G32(0x40400000)),
+            "0x40400000: +2^(-128 + 0/2^32)"
+        );
+        assert_eq!(
+            format!("{:?}", G32(0xC0000003)),
+            "0xC0000003: -2^(-149 + 2147483648/2^32)"
+        );
+        assert_eq!(format!("{:?}", G32::ZERO), "0x40000000: ZERO");
+        assert_eq!(format!("{:?}", G32::NAR), "0xC0000000: NaR");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let two = G32(0x00800000);
+        let four = G32(0x01000000);
+        let half = G32(0x7F800000);
+        assert_eq!(two * two, four);
+        assert_eq!(four / two, two);
+        assert_eq!(two / four, half);
+        assert_eq!(four.sqrt(), two);
+        assert_eq!(G32(0x00000001).sqrt(), G32(0x00000000));
+        assert_eq!(G32(0x00000003).sqrt(), G32(0x00000002));
+        assert_eq!(G32(0x80800000) * two, G32(0x81000000));
+
+        // Halving the smallest normal value steps through the subnormal binades.
+        let mut x = G32(0x40800000);
+        for bits in [0x40400000, 0x40200000, 0x40100000] {
+            x /= two;
+            assert_eq!(x, G32(bits));
+            assert!(x.is_subnormal());
+        }
+        assert_eq!(G32(0x40000002) / two, G32(0x40000001));
+        assert_eq!(G32(0x40000001) / two, G32::ZERO);
+        assert_eq!(G32(0xC0000001) / two, G32::ZERO);
+        assert_eq!(G32(0x40000001) * G32(0x7FE00000), G32(0x40000001));
+        assert_eq!(G32(0x40000001) * G32(0x7FC00000), G32::ZERO);
+        assert_eq!(G32(0x40000001).sqrt(), G32(0x5A800000));
+        assert_eq!(G32(0x5A800000) * G32(0x5A800000), G32(0x40000001));
+        assert_eq!(G32(0x40400001) * four, G32(0x41000002));
+
+        assert_eq!(G32(0x3FFFFFFF) * two, G32(0x3FFFFFFF));
+        assert_eq!(G32(0xBFFFFFFF) * two, G32(0xBFFFFFFF));
+        assert_eq!(G32::ZERO * G32(0x3FFFFFFF), G32::ZERO);
+        assert_eq!(G32::ZERO * G32::NAR, G32::NAR);
+        assert_eq!(G32::ZERO / two, G32::ZERO);
+        assert_eq!(two / G32::ZERO, G32::NAR);
+        assert_eq!(G32::NAR.sqrt(), G32::NAR);
+        assert_eq!(G32(0x80000000).sqrt(), G32::NAR);
+        assert_eq!(G32::ZERO.sqrt(), G32::ZERO);
+        assert!(!G32::ZERO.is_subnormal());
+        assert!(!G32::NAR.is_subnormal());
+        assert!(!G32(0x40800000).is_subnormal());
+
+        for bits in [0x40800001, 0x12345678, 0x3FFFFFFF, 0x40000001, 0xDEADBEEF] {
+            assert_eq!(G32(bits) * G32::ONE, G32(bits));
+            assert_eq!(G32(bits) / G32::ONE, G32(bits));
+        }
+    }
+}
@@ -1,7 +1,18 @@
-use core::{fmt, ops::*};
+use crate::{
+    error::{ParseLnError, TryFromFloatError},
+    exception, golden,
+};
+use core::{fmt, ops::*, str::FromStr};
 
+/// A 32 bit logarithmic number.
+///
+/// The most significant bit is the sign, the remaining 31 bits hold the base 2 logarithm of the
+/// magnitude as a two's complement fixed point number with 8 integer bits and 23 fraction bits.
+/// The most negative logarithm is reserved: it encodes ZERO with the sign clear and NaR with the
+/// sign set.
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
 pub struct L32(u32);
 
 impl L32 {
@@ -15,6 +26,9 @@ impl L32 {
     /// The value 1.0
     pub const ONE: Self = Self(0);
 
+    /// Number of fraction bits in the logarithm.
+    pub const FRACTION_BITS: u32 = 23;
+
     /// Raw transmutation to u32.
     #[inline]
     pub const fn to_bits(self) -> u32 {
@@ -27,32 +41,513 @@ impl L32 {
         Self(bits)
     }
 
-    /// Calculates the square root.
+    /// Returns the raw bits as a byte array in little endian order.
+    #[inline]
+    pub const fn to_le_bytes(self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+
+    /// Returns the raw bits as a byte array in big endian order.
+    #[inline]
+    pub const fn to_be_bytes(self) -> [u8; 4] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns the raw bits as a byte array in native order.
+    #[inline]
+    pub const fn to_ne_bytes(self) -> [u8; 4] {
+        self.0.to_ne_bytes()
+    }
+
+    /// Creates a value from its raw bits as a byte array in little endian order.
+    #[inline]
+    pub const fn from_le_bytes(bytes: [u8; 4]) -> Self {
+        Self(u32::from_le_bytes(bytes))
+    }
+
+    /// Creates a value from its raw bits as a byte array in big endian order.
+    #[inline]
+    pub const fn from_be_bytes(bytes: [u8; 4]) -> Self {
+        Self(u32::from_be_bytes(bytes))
+    }
+
+    /// Creates a value from its raw bits as a byte array in native order.
+    #[inline]
+    pub const fn from_ne_bytes(bytes: [u8; 4]) -> Self {
+        Self(u32::from_ne_bytes(bytes))
+    }
+
+    /// Returns `true` if the sign bit is set.
     ///
-    /// Returns NaR if the input is negative or NaR.
+    /// This is the case for negative values and NaR.
     #[inline]
-    pub fn sqrt(self) -> Self {
-        // We don't care about the sign bit because if it's set the result will be overwritten
-        // with NaR anyway.
-        let exp = self.0 >> 1;
-        let exp_sign = self.0 & 0x40000000;
-        let mut res = Self(exp_sign | exp);
+    pub const fn sign(self) -> bool {
+        self.0 & 0x80000000 != 0
+    }
+
+    /// Integer part of the logarithm, rounded towards negative infinity.
+    ///
+    /// For ZERO and NaR this is -128.
+    #[inline]
+    pub const fn exponent(self) -> i8 {
+        (self.raw_exp() >> Self::FRACTION_BITS) as i8
+    }
+
+    /// Fraction bits of the logarithm.
+    ///
+    /// The real value is `2^(exponent + fraction / 2^FRACTION_BITS)` with the sign applied.
+    #[inline]
+    pub const fn fraction(self) -> u32 {
+        self.0 & 0x7FFFFF
+    }
+
+    /// Assembles a value from its sign, exponent and fraction.
+    ///
+    /// This is the inverse of [`sign`](Self::sign), [`exponent`](Self::exponent) and
+    /// [`fraction`](Self::fraction). Only the low [`FRACTION_BITS`](Self::FRACTION_BITS) bits of
+    /// `fraction` are used.
+    #[inline]
+    pub const fn from_parts(sign: bool, exponent: i8, fraction: u32) -> Self {
+        let sign = (sign as u32) << 31;
+        let exp = ((exponent as u32) << Self::FRACTION_BITS) & 0x7F800000;
+        Self(sign | exp | (fraction & 0x7FFFFF))
+    }
+
+    /// Returns the absolute value. NaR stays NaR.
+    #[inline]
+    pub const fn abs(self) -> Self {
+        let mut res = Self(self.0 & 0x7FFFFFFF);
+
+        if self.0 == Self::NAR.0 {
+            res = Self::NAR;
+        }
+
+        res
+    }
+
+    /// Returns the magnitude of `self` with the sign bit of `sign`.
+    ///
+    /// ZERO and NaR are returned as is.
+    #[inline]
+    pub const fn copysign(self, sign: Self) -> Self {
+        let mut res = Self((self.0 & 0x7FFFFFFF) | (sign.0 & 0x80000000));
+
+        if self.0 == Self::ZERO.0 || self.0 == Self::NAR.0 {
+            res = self;
+        }
+
+        res
+    }
+
+    /// Returns the smaller value, NaR is only returned if both values are NaR.
+    #[inline]
+    pub const fn min(self, rhs: Self) -> Self {
+        // NaR sorts first, wrapping it around makes it lose against everything else.
+        let a = self.to_sortable_bits().wrapping_sub(1);
+        let b = rhs.to_sortable_bits().wrapping_sub(1);
+        Self::from_sortable_bits(if a < b { a } else { b }.wrapping_add(1))
+    }
+
+    /// Returns the larger value, NaR is only returned if both values are NaR.
+    #[inline]
+    pub const fn max(self, rhs: Self) -> Self {
+        // NaR sorts first, so it only wins if both values are NaR.
+        let a = self.to_sortable_bits();
+        let b = rhs.to_sortable_bits();
+        Self::from_sortable_bits(if a > b { a } else { b })
+    }
+
+    /// Multiplies by `2^n`.
+    ///
+    /// This is exact, only the integer part of the exponent changes. Like with multiplication,
+    /// the exponent wraps around if the result is out of range.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn mul_pow2(self, n: i32) -> Self {
+        self.scale_pow2(n.into())
+    }
+
+    /// [`mul_pow2`](Self::mul_pow2) with a wider `n`, so shifts can negate it without
+    /// overflowing.
+    #[inline]
+    fn scale_pow2(self, n: i64) -> Self {
+        let exp = self.0.wrapping_add((n as u32) << Self::FRACTION_BITS) & 0x7FFFFFFF;
+        let mut res = Self((self.0 & 0x80000000) | exp);
 
         if self == Self::ZERO {
             res = Self::ZERO;
         }
-        if self.0 & 0x80000000 != 0 {
+        if self == Self::NAR {
+            res = Self::NAR;
+        }
+
+        let exp = i64::from(self.raw_exp()) + (n << Self::FRACTION_BITS);
+        exception::check("mul_pow2", &[self], Some(exp), false, res);
+
+        res
+    }
+
+    /// Returns the integer part of the logarithm of the magnitude.
+    ///
+    /// Returns `None` for ZERO and NaR.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn logb(self) -> Option<i8> {
+        if self == Self::ZERO || self == Self::NAR {
+            None
+        } else {
+            Some(self.exponent())
+        }
+    }
+
+    /// Splits the value into a residual with a magnitude in `[1, 2)` and a power of two.
+    ///
+    /// `self == residual.mul_pow2(exp)`. Note that unlike C's `frexp` the residual is not in
+    /// `[0.5, 1)`. ZERO and NaR are returned as is with an exponent of 0.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn frexp(self) -> (Self, i8) {
+        let mut res = (Self(self.0 & 0x807FFFFF), self.exponent());
+
+        if self == Self::ZERO || self == Self::NAR {
+            res = (self, 0);
+        }
+
+        res
+    }
+
+    /// Converts an f32 without rounding.
+    ///
+    /// Returns `None` unless the value is zero or a power of two in the range of L32, the only
+    /// floats that are exactly representable. Infinities and NaN also give `None`.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn from_f32_exact(x: f32) -> Option<Self> {
+        Self::try_from(x).ok()
+    }
+
+    /// Converts to an f32 without rounding.
+    ///
+    /// Returns `None` if the fraction of the logarithm is not zero, or for NaR.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn to_f32_exact(self) -> Option<f32> {
+        if self == Self::ZERO {
+            return Some(0.0);
+        }
+        if self == Self::NAR || self.fraction() != 0 {
+            return None;
+        }
+
+        let exp = i32::from(self.exponent());
+        let magnitude = if exp >= -126 {
+            ((exp + 127) as u32) << 23
+        } else {
+            1 << (exp + 149)
+        };
+        Some(f32::from_bits((self.0 & 0x80000000) | magnitude))
+    }
+
+    /// The exponent as a signed fixed point number with 23 fraction bits.
+    #[inline]
+    pub(crate) const fn raw_exp(self) -> i32 {
+        (self.0 << 1) as i32 >> 1
+    }
+
+    /// Maps the value to a u32 whose unsigned ordering matches the numeric ordering.
+    ///
+    /// NaR is ordered below every other value. This gives a total order suitable for radix
+    /// sorting and as a key in ordered containers.
+    #[inline]
+    pub const fn to_sortable_bits(self) -> u32 {
+        // Flipping the sign of the exponent makes its magnitude increase from ZERO upwards.
+        let magnitude = (self.0 ^ 0x40000000) & 0x7FFFFFFF;
+        let mut res = if self.0 & 0x80000000 != 0 {
+            0x80000000 - magnitude
+        } else {
+            0x80000000 | magnitude
+        };
+
+        if self.0 == Self::NAR.0 {
+            res = 0;
+        }
+
+        res
+    }
+
+    /// Inverse of [`to_sortable_bits`](Self::to_sortable_bits).
+    #[inline]
+    pub const fn from_sortable_bits(bits: u32) -> Self {
+        let mut res = if bits & 0x80000000 != 0 {
+            Self((bits & 0x7FFFFFFF) ^ 0x40000000)
+        } else {
+            Self(0x80000000 | ((0x80000000 - bits) ^ 0x40000000))
+        };
+
+        if bits == 0 {
             res = Self::NAR;
         }
 
         res
     }
+
+    /// Calculates the square root.
+    ///
+    /// The logarithm is halved and rounded to nearest, ties to even.
+    /// Returns NaR if the input is negative or NaR.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn sqrt(self) -> Self {
+        let res = Self(golden::l32_sqrt_bits(self.0));
+
+        exception::check("sqrt", &[self], None, self.0 & 1 != 0, res);
+
+        res
+    }
+
+    /// Calculates the square root, returning `None` if the input is negative or NaR.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn checked_sqrt(self) -> Option<Self> {
+        if self.sign() {
+            None
+        } else {
+            Some(self.sqrt())
+        }
+    }
+
+    /// Converts radians to degrees.
+    ///
+    /// The result is correctly rounded.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn to_degrees(self) -> Self {
+        // log2(180 / π), rounded to nearest.
+        const DEGREES_PER_RADIAN: L32 = L32(0x02EB90D1);
+        self * DEGREES_PER_RADIAN
+    }
+
+    /// Converts degrees to radians.
+    ///
+    /// The result is correctly rounded.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn to_radians(self) -> Self {
+        // log2(π / 180), rounded to nearest.
+        const RADIANS_PER_DEGREE: L32 = L32(0x7D146F2F);
+        self * RADIANS_PER_DEGREE
+    }
+
+    /// Calculates the geometric midpoint of two values.
+    ///
+    /// This is `sqrt(self * rhs)` with the sign of the operands. The logarithms are averaged
+    /// without forming the product, so it never overflows, and rounded to nearest, ties to even.
+    /// Returns ZERO if either value is ZERO, and NaR if the signs differ or either value is NaR.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    pub fn midpoint(self, rhs: Self) -> Self {
+        let res = Self(golden::l32_midpoint_bits(self.0, rhs.0));
+
+        let sum = self.raw_exp() + rhs.raw_exp();
+        exception::check("midpoint", &[self, rhs], None, sum & 1 != 0, res);
+
+        res
+    }
+
+    /// Multiplies without handling ZERO, NaR and overflow.
+    ///
+    /// This saves the selects of the special cases in tight loops over validated data. No
+    /// exceptions are reported.
+    ///
+    /// # Safety
+    /// Neither operand may be ZERO or NaR, and the exact result must be in range. The compiler
+    /// assumes this holds, so other operands are undefined behavior. Debug builds panic instead.
+    #[inline]
+    pub unsafe fn unchecked_mul(self, rhs: Self) -> Self {
+        let exp = i64::from(self.raw_exp()) + i64::from(rhs.raw_exp());
+        let valid = |x: Self| x != Self::ZERO && x != Self::NAR;
+        debug_assert!(valid(self) && valid(rhs));
+        debug_assert!(-0x40000000 < exp && exp < 0x40000000);
+        // SAFETY: Guaranteed by the caller.
+        unsafe {
+            core::hint::assert_unchecked(valid(self) && valid(rhs));
+            core::hint::assert_unchecked(-0x40000000 < exp && exp < 0x40000000);
+        }
+
+        let sign = (self.0 ^ rhs.0) & 0x80000000;
+        Self(sign | (self.0.wrapping_add(rhs.0) & 0x7FFFFFFF))
+    }
+
+    /// Divides without handling ZERO, NaR and overflow.
+    ///
+    /// This saves the selects of the special cases in tight loops over validated data. No
+    /// exceptions are reported.
+    ///
+    /// # Safety
+    /// Neither operand may be ZERO or NaR, and the exact result must be in range. The compiler
+    /// assumes this holds, so other operands are undefined behavior. Debug builds panic instead.
+    #[inline]
+    pub unsafe fn unchecked_div(self, rhs: Self) -> Self {
+        let exp = i64::from(self.raw_exp()) - i64::from(rhs.raw_exp());
+        let valid = |x: Self| x != Self::ZERO && x != Self::NAR;
+        debug_assert!(valid(self) && valid(rhs));
+        debug_assert!(-0x40000000 < exp && exp < 0x40000000);
+        // SAFETY: Guaranteed by the caller.
+        unsafe {
+            core::hint::assert_unchecked(valid(self) && valid(rhs));
+            core::hint::assert_unchecked(-0x40000000 < exp && exp < 0x40000000);
+        }
+
+        let sign = (self.0 ^ rhs.0) & 0x80000000;
+        Self(sign | (self.0.wrapping_sub(rhs.0) & 0x7FFFFFFF))
+    }
+}
+
+impl TryFrom<f32> for L32 {
+    type Error = TryFromFloatError;
+
+    /// Converts without rounding, see [`from_f32_exact`](Self::from_f32_exact).
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    fn try_from(x: f32) -> Result<Self, Self::Error> {
+        let bits = x.to_bits();
+        let biased = (bits >> 23) & 0xFF;
+        let mantissa = bits & 0x7FFFFF;
+
+        let exp = match (biased, mantissa) {
+            (0, 0) => return Ok(Self::ZERO),
+            (0xFF, 0) => return Err(TryFromFloatError::Infinite),
+            (0xFF, _) => return Err(TryFromFloatError::Nan),
+            (0, m) if m.is_power_of_two() => m.trailing_zeros() as i32 - 149,
+            (e, 0) => e as i32 - 127,
+            _ => return Err(TryFromFloatError::Inexact),
+        };
+        match i8::try_from(exp) {
+            // 2^-128 would be the bit pattern of ZERO.
+            Ok(exp) if exp != i8::MIN => Ok(Self::from_parts(bits >> 31 != 0, exp, 0)),
+            _ => Err(TryFromFloatError::Underflow),
+        }
+    }
+}
+
+impl FromStr for L32 {
+    type Err = ParseLnError;
+
+    /// Parses a value without any rounding.
+    ///
+    /// Only exact syntaxes are supported for now:
+    /// - `NaR`.
+    /// - Raw bits, `l:` followed by up to 8 hex digits, e.g. `l:0x40000000` or `l:40000000`.
+    /// - Hex floats with a sign and binary exponent, e.g. `0x1p3`, `-0x0.4p-1` or `0x0p0`.
+    ///   Like with [`from_f32_exact`](Self::from_f32_exact), only zero and powers of two are
+    ///   representable, anything else is [`ParseLnError::Inexact`].
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseLnError::Empty);
+        }
+        if s == "NaR" {
+            return Ok(Self::NAR);
+        }
+        if let Some(hex) = s.strip_prefix("l:") {
+            let hex = hex.strip_prefix("0x").unwrap_or(hex);
+            if hex.is_empty() || hex.len() > 8 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(ParseLnError::InvalidSyntax);
+            }
+            return Ok(Self(
+                hex.bytes().fold(0, |bits, b| bits << 4 | hex_digit(b)),
+            ));
+        }
+
+        let (sign, s) = split_sign(s);
+        let s = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .ok_or(ParseLnError::InvalidSyntax)?;
+        let (mantissa, exp) =
+            split_once(s, |b| b == b'p' || b == b'P').ok_or(ParseLnError::InvalidSyntax)?;
+        let (int, frac) = split_once(mantissa, |b| b == b'.').unwrap_or((mantissa, ""));
+        let digits = int.bytes().chain(frac.bytes());
+        if int.len() + frac.len() == 0 || !digits.clone().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseLnError::InvalidSyntax);
+        }
+        let (exp_negative, exp_digits) = split_sign(exp);
+        if exp_digits.is_empty() || !exp_digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseLnError::InvalidSyntax);
+        }
+
+        // The significand is a power of two if its only nonzero digit is.
+        let mut digits = digits.enumerate();
+        let Some((pos, lead)) = digits.find(|&(_, b)| b != b'0') else {
+            return Ok(Self::ZERO);
+        };
+        let lead = hex_digit(lead);
+        if !lead.is_power_of_two() || digits.any(|(_, b)| b != b'0') {
+            return Err(ParseLnError::Inexact);
+        }
+
+        // Saturate huge exponents, they are out of range either way.
+        let exp = exp_digits
+            .bytes()
+            .fold(0i64, |exp, b| (exp * 10 + i64::from(b - b'0')).min(1 << 40));
+        let exp = if exp_negative { -exp } else { exp };
+        let int_digits = int.len() as i64 - pos as i64 - 1;
+        let exp = exp + i64::from(lead.trailing_zeros()) + 4 * int_digits;
+
+        match exp {
+            ..-127 => Err(ParseLnError::Underflow),
+            128.. => Err(ParseLnError::Overflow),
+            _ => Ok(Self::from_parts(sign, exp as i8, 0)),
+        }
+    }
+}
+
+/// Value of an ASCII hex digit, 0 for anything else.
+fn hex_digit(b: u8) -> u32 {
+    let digit = match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'f' => b - b'a' + 10,
+        b'A'..=b'F' => b - b'A' + 10,
+        _ => 0,
+    };
+    digit.into()
+}
+
+/// Like `str::split_once`, but without a panic path.
+fn split_once(s: &str, f: impl Fn(u8) -> bool) -> Option<(&str, &str)> {
+    let i = s.bytes().position(f)?;
+    Some((s.get(..i)?, s.get(i + 1..)?))
+}
+
+/// Splits an optional `+` or `-` off a string, returning whether it was `-`.
+fn split_sign(s: &str) -> (bool, &str) {
+    if let Some(rest) = s.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, s.strip_prefix('+').unwrap_or(s))
+    }
 }
 
 impl fmt::Debug for L32 {
+    /// Prints the raw bits in the syntax accepted by [`FromStr`], e.g. `l:0x40000000`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // TODO: proper formatting
-        self.0.fmt(f)
+        write!(f, "l:{:#010X}", self.0)
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for L32 {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        // Same output as `Debug`.
+        f.write_str("l:0x")?;
+        for shift in (0..32).step_by(4).rev() {
+            let digit = char::from_digit((self.0 >> shift) & 0xF, 16).unwrap_or('0');
+            ufmt::uDisplay::fmt(&digit.to_ascii_uppercase(), f)?;
+        }
+        Ok(())
     }
 }
 
@@ -60,17 +555,14 @@ impl Mul<L32> for L32 {
     type Output = Self;
 
     #[inline]
+    // Multiplication adds the logarithms.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
     fn mul(self, rhs: L32) -> Self {
-        let sign = (self.0 ^ rhs.0) & 0x80000000;
-        let exp = self.0.wrapping_add(rhs.0) & 0x7FFFFFFF;
-        let mut res = Self(sign | exp);
+        let res = Self(golden::l32_mul_bits(self.0, rhs.0));
 
-        if self == Self::ZERO || rhs == Self::ZERO {
-            res = Self::ZERO;
-        }
-        if self == Self::NAR || rhs == Self::NAR {
-            res = Self::NAR;
-        }
+        let exp = i64::from(self.raw_exp()) + i64::from(rhs.raw_exp());
+        exception::check("mul", &[self, rhs], Some(exp), false, res);
 
         res
     }
@@ -78,6 +570,7 @@ impl Mul<L32> for L32 {
 
 impl MulAssign<L32> for L32 {
     #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
     fn mul_assign(&mut self, rhs: L32) {
         *self = *self * rhs;
     }
@@ -87,17 +580,14 @@ impl Div<L32> for L32 {
     type Output = L32;
 
     #[inline]
+    // Division subtracts the logarithms.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
     fn div(self, rhs: L32) -> Self::Output {
-        let sign = (self.0 ^ rhs.0) & 0x80000000;
-        let exp = self.0.wrapping_sub(rhs.0) & 0x7FFFFFFF;
-        let mut res = Self(sign | exp);
+        let res = Self(golden::l32_div_bits(self.0, rhs.0));
 
-        if self == Self::ZERO {
-            res = Self::ZERO;
-        }
-        if self == Self::NAR || rhs == Self::NAR || rhs == Self::ZERO {
-            res = Self::NAR;
-        }
+        let exp = i64::from(self.raw_exp()) - i64::from(rhs.raw_exp());
+        exception::check("div", &[self, rhs], Some(exp), false, res);
 
         res
     }
@@ -105,11 +595,51 @@ impl Div<L32> for L32 {
 
 impl DivAssign<L32> for L32 {
     #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
     fn div_assign(&mut self, rhs: L32) {
         *self = *self / rhs;
     }
 }
 
+impl Shl<i32> for L32 {
+    type Output = Self;
+
+    /// Multiplies by `2^n`, see [`mul_pow2`](Self::mul_pow2).
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    fn shl(self, n: i32) -> Self {
+        self.mul_pow2(n)
+    }
+}
+
+impl ShlAssign<i32> for L32 {
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    fn shl_assign(&mut self, n: i32) {
+        *self = *self << n;
+    }
+}
+
+impl Shr<i32> for L32 {
+    type Output = Self;
+
+    /// Divides by `2^n`, see [`mul_pow2`](Self::mul_pow2).
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    fn shr(self, n: i32) -> Self {
+        // -i32::MIN doesn't fit in an i32.
+        self.scale_pow2(-i64::from(n))
+    }
+}
+
+impl ShrAssign<i32> for L32 {
+    #[inline]
+    #[cfg_attr(feature = "no-panic", no_panic::no_panic)]
+    fn shr_assign(&mut self, n: i32) {
+        *self = *self >> n;
+    }
+}
+
 impl Default for L32 {
     #[inline]
     fn default() -> Self {
@@ -120,8 +650,318 @@ impl Default for L32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::random_bits;
+
+    #[test]
+    fn bytes() {
+        let x = L32(0x12345678);
+        assert_eq!(x.to_le_bytes(), [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(x.to_be_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(x.to_ne_bytes(), 0x12345678u32.to_ne_bytes());
+        assert_eq!(L32::from_le_bytes([0x78, 0x56, 0x34, 0x12]), x);
+        assert_eq!(L32::from_be_bytes([0x12, 0x34, 0x56, 0x78]), x);
+        assert_eq!(L32::from_ne_bytes(x.to_ne_bytes()), x);
+    }
+
+    #[test]
+    fn parts() {
+        fn test(bits: u32, sign: bool, exponent: i8, fraction: u32) {
+            let x = L32(bits);
+            assert_eq!(
+                (x.sign(), x.exponent(), x.fraction()),
+                (sign, exponent, fraction)
+            );
+            assert_eq!(L32::from_parts(sign, exponent, fraction), x);
+        }
+
+        test(0x00000000, false, 0, 0);
+        test(0x00800000, false, 1, 0);
+        test(0x00C00000, false, 1, 0x400000);
+        test(0x3FFFFFFF, false, 127, 0x7FFFFF);
+        test(0x7FFFFFFF, false, -1, 0x7FFFFF);
+        test(0x7F800000, false, -1, 0);
+        test(0x40000001, false, -128, 1);
+        test(0x80000000, true, 0, 0);
+        test(0xDEADBEEF, true, -67, 0x2DBEEF);
+        test(0x40000000, false, -128, 0);
+        test(0xC0000000, true, -128, 0);
+
+        assert_eq!(L32::from_parts(false, 1, 0xFF800001), L32(0x00800001));
+    }
+
+    #[test]
+    fn abs_copysign() {
+        assert_eq!(L32(0x80800000).abs(), L32(0x00800000));
+        assert_eq!(L32(0x00800000).abs(), L32(0x00800000));
+        assert_eq!(L32(0xFFFFFFFF).abs(), L32(0x7FFFFFFF));
+        assert_eq!(L32(0xC0000001).abs(), L32(0x40000001));
+        assert_eq!(L32::ZERO.abs(), L32::ZERO);
+        assert_eq!(L32::NAR.abs(), L32::NAR);
+
+        assert_eq!(L32(0x00800000).copysign(L32(0x80000000)), L32(0x80800000));
+        assert_eq!(L32(0x80800000).copysign(L32::ONE), L32(0x00800000));
+        assert_eq!(L32(0x80800000).copysign(L32(0x80000001)), L32(0x80800000));
+        assert_eq!(L32(0x00800000).copysign(L32::NAR), L32(0x80800000));
+        assert_eq!(L32(0x00800000).copysign(L32::ZERO), L32(0x00800000));
+        assert_eq!(L32::ZERO.copysign(L32(0x80800000)), L32::ZERO);
+        assert_eq!(L32::NAR.copysign(L32::ONE), L32::NAR);
+    }
+
+    #[test]
+    fn min_max() {
+        fn test(a: u32, b: u32, min: u32, max: u32) {
+            assert_eq!(
+                (L32(a).min(L32(b)), L32(a).max(L32(b))),
+                (L32(min), L32(max))
+            );
+            assert_eq!(
+                (L32(b).min(L32(a)), L32(b).max(L32(a))),
+                (L32(min), L32(max))
+            );
+        }
+
+        test(0x00800000, 0x80800000, 0x80800000, 0x00800000);
+        test(0x00800000, 0x00000000, 0x00000000, 0x00800000);
+        test(0x80800000, 0x80000000, 0x80800000, 0x80000000);
+        test(0x40000000, 0x80000000, 0x80000000, 0x40000000);
+        test(0x40000000, 0x40000001, 0x40000000, 0x40000001);
+        test(0x3FFFFFFF, 0xBFFFFFFF, 0xBFFFFFFF, 0x3FFFFFFF);
+        test(0xC0000000, 0x00800000, 0x00800000, 0x00800000);
+        test(0xC0000000, 0xBFFFFFFF, 0xBFFFFFFF, 0xBFFFFFFF);
+        test(0xC0000000, 0x40000000, 0x40000000, 0x40000000);
+        test(0xC0000000, 0xC0000000, 0xC0000000, 0xC0000000);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn mul_pow2() {
+        fn test(a: u32, n: i32, res: u32) {
+            assert_eq!(L32(a).mul_pow2(n), L32(res));
+            if let Ok(exp) = i8::try_from(n) {
+                assert_eq!(L32(a).mul_pow2(n), L32(a) * L32::from_parts(false, exp, 0));
+            }
+        }
+
+        test(0x00000000, 0, 0x00000000);
+        test(0x00000000, 1, 0x00800000);
+        test(0x00000000, -1, 0x7F800000);
+        test(0x80123456, 5, 0x82923456);
+        test(0x3F812345, -127, 0x00012345);
+        test(0x40000001, 127, 0x7F800001);
+        // Overflow
+        test(0x3F800000, 1, 0x40000000);
+        test(0xBF800000, 1, 0xC0000000);
+        test(0x00000000, 256, 0x00000000);
+        // ZERO and NaR
+        test(0x40000000, 1, 0x40000000);
+        test(0x40000000, -1, 0x40000000);
+        test(0xC0000000, 1, 0xC0000000);
+        test(0xC0000000, -1, 0xC0000000);
+    }
+
+    #[test]
+    fn shift() {
+        let mut x = L32(0x00C00000);
+        assert_eq!(x << 2, L32(0x01C00000));
+        assert_eq!(x >> 2, L32(0x7FC00000));
+        assert_eq!(x >> -2, x << 2);
+        // Both wrap around the same way, 2^31 is a multiple of the 256 exponents.
+        assert_eq!(x >> i32::MIN, x);
+        assert_eq!(x << i32::MIN, x);
+        let mut state = 0x3C6EF372FE94F82Bu64;
+        let random = core::iter::repeat_with(|| random_bits(&mut state) as i32).take(1000);
+        let edges = [
+            i32::MIN,
+            i32::MIN + 1,
+            -257,
+            -256,
+            -129,
+            -128,
+            -1,
+            0,
+            1,
+            127,
+            128,
+            i32::MAX,
+        ];
+        for n in edges.into_iter().chain(random) {
+            for y in [x, L32(0xBF812345), L32(0x40000001), L32::ZERO, L32::NAR] {
+                assert_eq!(y >> n, y << n.wrapping_neg(), "{y:?} >> {n}");
+                assert_eq!(y >> n, y.mul_pow2(n.wrapping_neg()), "{y:?} >> {n}");
+            }
+        }
+        assert_eq!(L32::ZERO << 5, L32::ZERO);
+        assert_eq!(L32::NAR >> 5, L32::NAR);
+        x <<= 3;
+        assert_eq!(x, L32(0x01C00000).mul_pow2(1));
+        x >>= 3;
+        assert_eq!(x, L32(0x00C00000));
+    }
+
+    #[test]
+    fn logb_frexp() {
+        fn test(a: u32, logb: Option<i8>, residual: u32, exp: i8) {
+            assert_eq!(L32(a).logb(), logb);
+            assert_eq!(L32(a).frexp(), (L32(residual), exp));
+            assert_eq!(L32(residual).mul_pow2(exp.into()), L32(a));
+        }
+
+        test(0x00000000, Some(0), 0x00000000, 0);
+        test(0x00C00000, Some(1), 0x00400000, 1);
+        test(0x7FFFFFFF, Some(-1), 0x007FFFFF, -1);
+        test(0xDEADBEEF, Some(-67), 0x802DBEEF, -67);
+        test(0x3FFFFFFF, Some(127), 0x007FFFFF, 127);
+        test(0x40000001, Some(-128), 0x00000001, -128);
+        test(0x40000000, None, 0x40000000, 0);
+        test(0xC0000000, None, 0xC0000000, 0);
+    }
+
+    #[test]
+    fn f32_exact() {
+        fn test(x: f32, bits: Option<u32>) {
+            assert_eq!(L32::from_f32_exact(x), bits.map(L32));
+            if let Some(bits) = bits {
+                assert_eq!(
+                    L32(bits).to_f32_exact().map(f32::to_bits),
+                    Some(x.to_bits())
+                );
+            }
+        }
+
+        test(0.0, Some(0x40000000));
+        test(1.0, Some(0x00000000));
+        test(-2.0, Some(0x80800000));
+        test(0.25, Some(0x7F000000));
+        test(2f32.powi(127), Some(0x3F800000));
+        test(2f32.powi(-126), Some(0x41000000));
+        test(f32::from_bits(0x00400000), Some(0x40800000));
+        test(f32::from_bits(0x80400000), Some(0xC0800000));
+        test(f32::from_bits(0x00200000), None);
+        test(f32::from_bits(1), None);
+        test(3.0, None);
+        test(1.5, None);
+        test(f32::from_bits(0x00300000), None);
+        test(f32::INFINITY, None);
+        test(f32::NAN, None);
+
+        assert_eq!(L32::from_f32_exact(-0.0), Some(L32::ZERO));
+        assert_eq!(L32::try_from(8.0), Ok(L32(0x01800000)));
+        assert_eq!(L32::try_from(3.0), Err(TryFromFloatError::Inexact));
+        assert_eq!(
+            L32::try_from(f32::from_bits(0x00300000)),
+            Err(TryFromFloatError::Inexact)
+        );
+        assert_eq!(
+            L32::try_from(f32::from_bits(1)),
+            Err(TryFromFloatError::Underflow)
+        );
+        assert_eq!(
+            L32::try_from(f32::NEG_INFINITY),
+            Err(TryFromFloatError::Infinite)
+        );
+        assert_eq!(L32::try_from(f32::NAN), Err(TryFromFloatError::Nan));
+        assert_eq!(L32::NAR.to_f32_exact(), None);
+        assert_eq!(L32(0x00000001).to_f32_exact(), None);
+        assert_eq!(L32(0x40000001).to_f32_exact(), None);
+    }
+
+    #[cfg(feature = "panic-on-nar")]
+    #[test]
+    fn panic_on_nar() {
+        extern crate std;
+        use std::panic::catch_unwind;
+
+        // NaR operands are fine.
+        assert_eq!(L32::NAR * L32::ONE, L32::NAR);
+        assert_eq!(L32::ONE / L32::NAR, L32::NAR);
+        assert_eq!(L32::NAR.sqrt(), L32::NAR);
+
+        assert!(catch_unwind(|| L32(0xBFFFFFFF) * L32(0x00000001)).is_err());
+        assert!(catch_unwind(|| L32::ONE / L32::ZERO).is_err());
+        assert!(catch_unwind(|| L32(0x80000000).sqrt()).is_err());
+        assert!(catch_unwind(|| L32(0x80000000).midpoint(L32::ONE)).is_err());
+        assert!(catch_unwind(|| L32(0xBF800000).mul_pow2(1)).is_err());
+    }
+
+    #[test]
+    fn sortable_bits() {
+        // In ascending numeric order.
+        let values = [
+            0xC0000000, 0xBFFFFFFF, 0xA0000000, 0x80000001, 0x80000000, 0xFFFFFFFF, 0xC0000001,
+            0x40000000, 0x40000001, 0x7FFFFFFF, 0x00000000, 0x00000001, 0x3FFFFFFF,
+        ];
+        for pair in values.windows(2) {
+            assert!(L32(pair[0]).to_sortable_bits() < L32(pair[1]).to_sortable_bits());
+        }
+        assert_eq!(L32::NAR.to_sortable_bits(), 0);
+        assert_eq!(L32::ZERO.to_sortable_bits(), 0x80000000);
+        assert_eq!(L32(0x3FFFFFFF).to_sortable_bits(), 0xFFFFFFFF);
+
+        for bits in values {
+            assert_eq!(
+                L32::from_sortable_bits(L32(bits).to_sortable_bits()),
+                L32(bits)
+            );
+        }
+        for key in [
+            0, 1, 0x12345678, 0x7FFFFFFF, 0x80000000, 0xDEADBEEF, 0xFFFFFFFF,
+        ] {
+            assert_eq!(L32::from_sortable_bits(key).to_sortable_bits(), key);
+        }
+    }
+
+    #[test]
+    fn debug() {
+        extern crate std;
+        use std::format;
+
+        assert_eq!(format!("{:?}", L32::ONE), "l:0x00000000");
+        assert_eq!(format!("{:?}", L32::ZERO), "l:0x40000000");
+        assert_eq!(format!("{:?}", L32::NAR), "l:0xC0000000");
+        assert_eq!(format!("{:?}", L32(0xDEADBEEF)), "l:0xDEADBEEF");
+
+        let mut state = 0x9E3779B97F4A7C15;
+        for _ in 0..1000 {
+            let x = L32(random_bits(&mut state));
+            assert_eq!(format!("{x:?}").parse(), Ok(x));
+        }
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn udebug() {
+        struct Buf {
+            bytes: [u8; 16],
+            len: usize,
+        }
+
+        impl ufmt::uWrite for Buf {
+            type Error = ();
+
+            fn write_str(&mut self, s: &str) -> Result<(), ()> {
+                let end = self.len + s.len();
+                self.bytes
+                    .get_mut(self.len..end)
+                    .ok_or(())?
+                    .copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+
+        let mut buf = Buf {
+            bytes: [0; 16],
+            len: 0,
+        };
+        ufmt::uwrite!(buf, "{:?}", L32::NAR).unwrap();
+        assert_eq!(&buf.bytes[..buf.len], b"l:0xC0000000");
+        buf.len = 0;
+        ufmt::uwrite!(buf, "{:?}", L32(0x0A1B2C3D)).unwrap();
+        assert_eq!(&buf.bytes[..buf.len], b"l:0x0A1B2C3D");
+    }
 
     #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
     fn sqrt() {
         assert_eq!(L32::NAR.sqrt(), L32::NAR);
         assert_eq!(L32(0x80000000).sqrt(), L32::NAR);
@@ -134,14 +974,169 @@ mod tests {
         assert_eq!(L32(0x00000001).sqrt(), L32(0x00000000));
         assert_eq!(L32(0x00800000).sqrt(), L32(0x00400000));
         assert_eq!(L32(0x00800001).sqrt(), L32(0x00400000));
-        assert_eq!(L32(0x3FFFFFFF).sqrt(), L32(0x1FFFFFFF));
+        assert_eq!(L32(0x00000003).sqrt(), L32(0x00000002));
+        assert_eq!(L32(0x3FFFFFFF).sqrt(), L32(0x20000000));
         assert_eq!(L32(0x70006101).sqrt(), L32(0x78003080));
-        assert_eq!(L32(0x7FFFFFFF).sqrt(), L32(0x7FFFFFFF));
+        assert_eq!(L32(0x7FFFFFFF).sqrt(), L32(0x00000000));
+        assert_eq!(L32(0x7FFFFFFD).sqrt(), L32(0x7FFFFFFE));
         assert_eq!(L32(0x40000001).sqrt(), L32(0x60000000));
         assert_eq!(L32(0x60000000).sqrt(), L32(0x70000000));
+
+        assert_eq!(L32::NAR.checked_sqrt(), None);
+        assert_eq!(L32(0x80000000).checked_sqrt(), None);
+        assert_eq!(L32(0x80800000).checked_sqrt(), None);
+        assert_eq!(L32(0xFFFFFFFF).checked_sqrt(), None);
+        assert_eq!(L32::ZERO.checked_sqrt(), Some(L32::ZERO));
+        assert_eq!(L32(0x00800000).checked_sqrt(), Some(L32(0x00400000)));
+        assert_eq!(L32(0x00000003).checked_sqrt(), Some(L32(0x00000002)));
+        assert_eq!(L32(0x00000001).checked_sqrt(), Some(L32(0x00000000)));
+
+        // Every pattern of the low 16 bits, at both ends of the range and around 1.0.
+        for i in 0..0x10000 {
+            for bits in [i, 0x3FFF0000 | i, 0x40000000 | i, 0x7FFF0000 | i] {
+                let x = L32(bits);
+                if x == L32::ZERO {
+                    continue;
+                }
+                let exp = x.raw_exp();
+                let res = x.sqrt().raw_exp();
+                let diff = 2 * res - exp;
+                assert!(diff == 0 || (diff.abs() == 1 && res & 1 == 0), "{x:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn angle_conversion() {
+        // 180, π
+        assert_eq!(L32(0x03BEF50B).to_radians(), L32(0x00D3643A));
+        assert_eq!(L32(0x00D3643A).to_degrees(), L32(0x03BEF50B));
+        // -90, -π/2
+        assert_eq!(L32(0x833EF50B).to_radians(), L32(0x8053643A));
+        assert_eq!(L32(0x8053643A).to_degrees(), L32(0x833EF50B));
+
+        assert_eq!(L32::ONE.to_degrees(), L32(0x02EB90D1));
+        assert_eq!(L32::ZERO.to_degrees(), L32::ZERO);
+        assert_eq!(L32::NAR.to_radians(), L32::NAR);
+        assert_eq!(L32(0xDEADBEEF).to_degrees().to_radians(), L32(0xDEADBEEF));
     }
 
     #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn midpoint() {
+        fn test(a: u32, b: u32, res: u32) {
+            assert_eq!(L32(a).midpoint(L32(b)), L32(res));
+            assert_eq!(L32(b).midpoint(L32(a)), L32(res));
+        }
+
+        test(0x00000000, 0x00000000, 0x00000000);
+        test(0x00800000, 0x01800000, 0x01000000);
+        test(0x80800000, 0x81800000, 0x81000000);
+        test(0x3FFFFFFF, 0x3FFFFFFF, 0x3FFFFFFF);
+        test(0x40000001, 0x40000001, 0x40000001);
+        test(0x3FFFFFFF, 0x40000001, 0x00000000);
+        test(0xBFFFFFFF, 0xC0000001, 0x80000000);
+        // Rounding
+        test(0x00000000, 0x00000001, 0x00000000);
+        test(0x00000001, 0x00000002, 0x00000002);
+        test(0x7FFFFFFF, 0x00000000, 0x00000000);
+        test(0x7FFFFFFF, 0x7FFFFFFE, 0x7FFFFFFE);
+        test(0x3FFFFFFF, 0x3FFFFFFE, 0x3FFFFFFE);
+        // ZERO
+        test(0x40000000, 0x40000000, 0x40000000);
+        test(0x40000000, 0x3FFFFFFF, 0x40000000);
+        test(0x40000000, 0xBFFFFFFF, 0x40000000);
+        // NaR
+        test(0x00800000, 0x80800000, 0xC0000000);
+        test(0x00000000, 0xC0000000, 0xC0000000);
+        test(0x40000000, 0xC0000000, 0xC0000000);
+        test(0xC0000000, 0xC0000000, 0xC0000000);
+    }
+
+    #[test]
+    fn parse() {
+        fn test(s: &str, res: Result<u32, ParseLnError>) {
+            assert_eq!(s.parse::<L32>(), res.map(L32));
+        }
+
+        test("NaR", Ok(0xC0000000));
+        test("l:0x40000000", Ok(0x40000000));
+        test("l:DEADBEEF", Ok(0xDEADBEEF));
+        test("l:1", Ok(0x00000001));
+        test("0x1p0", Ok(0x00000000));
+        test("0x1p3", Ok(0x01800000));
+        test("-0x1P+3", Ok(0x81800000));
+        test("+0x0.4p-1", Ok(0x7E800000));
+        test("0x0010.00p-4", Ok(0x00000000));
+        test("0X8p-3", Ok(0x00000000));
+        test("0x.8p1", Ok(0x00000000));
+        test("0x0p0", Ok(0x40000000));
+        test("-0x0.000p99999999999999999999", Ok(0x40000000));
+        test("0x1p127", Ok(0x3F800000));
+        test("0x1p-127", Ok(0x40800000));
+        test("0x1p128", Err(ParseLnError::Overflow));
+        test("0x1p-128", Err(ParseLnError::Underflow));
+        test("0x2p-128", Ok(0x40800000));
+        test("0x1p99999999999999999999", Err(ParseLnError::Overflow));
+        test("0x1.8p3", Err(ParseLnError::Inexact));
+        test("0x3p0", Err(ParseLnError::Inexact));
+        test("0x1.0001p0", Err(ParseLnError::Inexact));
+
+        test("", Err(ParseLnError::Empty));
+        let invalid = [
+            "nar",
+            "l:",
+            "l:0x",
+            "l:123456789",
+            "l:-1",
+            "l:0xg",
+            "1",
+            "1.0",
+            "0x1",
+            "0x1p",
+            "0xp0",
+            "0x.p0",
+            "0x1p-",
+            "0x1p0x",
+            "--0x1p0",
+            "0x1.2.3p0",
+            "-",
+            "0x1p 1",
+        ];
+        for s in invalid {
+            test(s, Err(ParseLnError::InvalidSyntax));
+        }
+    }
+
+    #[test]
+    fn unchecked() {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        for _ in 0..10000 {
+            // Exponents in [-32, 32) can't overflow.
+            let [a, b] = [random_bits(&mut state), random_bits(&mut state)].map(|bits| {
+                L32((bits & 0x80000000) | ((bits << 1) as i32 >> 3) as u32 & 0x7FFFFFFF)
+            });
+            if [a, b].iter().any(|&x| x == L32::ZERO || x == L32::NAR) {
+                continue;
+            }
+            // SAFETY: Neither operand is ZERO or NaR, and the result is in range.
+            unsafe {
+                assert_eq!(a.unchecked_mul(b), a * b);
+                assert_eq!(a.unchecked_div(b), a / b);
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn unchecked_zero() {
+        // SAFETY: Violates the contract on purpose, the debug assertion panics first.
+        let _ = unsafe { L32::ONE.unchecked_div(L32::ZERO) };
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
     fn mul() {
         fn test(a: u32, b: u32, res: u32) {
             assert_eq!(L32(a) * L32(b), L32(res));
@@ -172,6 +1167,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
     fn div() {
         fn test(a: u32, b: u32, res: u32) {
             assert_eq!(L32(a) / L32(b), L32(res));
@@ -209,4 +1205,320 @@ mod tests {
         test(0x80000000, 0x00000001, 0xFFFFFFFF);
         test(0xDEADBEEF, 0xBEEFDEAD, 0x1FBDE042);
     }
+
+    #[test]
+    fn round_trip() {
+        let mut state = 0x9E3779B97F4A7C15u64;
+        let special = [
+            0x00000000, 0x80000000, 0x40000000, 0xC0000000, 0x40000001, 0xC0000001, 0x3FFFFFFF,
+            0xBFFFFFFF, 0x7FFFFFFF, 0xFFFFFFFF, 0x00000001, 0x80000001,
+        ];
+        let random = core::iter::repeat_with(|| random_bits(&mut state)).take(100000);
+        for bits in special.into_iter().chain(random) {
+            let x = L32::from_bits(bits);
+            assert_eq!(x.to_bits(), bits);
+            assert_eq!(L32::from_sortable_bits(x.to_sortable_bits()), x);
+            assert_eq!(L32::from_sortable_bits(bits).to_sortable_bits(), bits);
+            assert_eq!(L32::from_parts(x.sign(), x.exponent(), x.fraction()), x);
+            let (residual, exp) = x.frexp();
+            assert_eq!(residual.mul_pow2(exp.into()), x);
+            if let Some(f) = x.to_f32_exact() {
+                assert_eq!(L32::from_f32_exact(f), Some(x));
+            }
+        }
+
+        for exp in -127..=127 {
+            for sign in [false, true] {
+                let x = L32::from_parts(sign, exp, 0);
+                let f = x.to_f32_exact().unwrap();
+                assert_eq!(f.abs(), 2f64.powi(exp.into()) as f32);
+                assert_eq!(f.is_sign_negative(), sign);
+                assert_eq!(L32::from_f32_exact(f), Some(x));
+            }
+        }
+    }
+
+    #[test]
+    fn monotonic() {
+        // Exact f32 conversion preserves order.
+        let powers = || core::iter::once(0x00400000).chain((1..=254).map(|e| e << 23));
+        let floats = powers().rev().map(|bits| -f32::from_bits(bits));
+        let floats = floats.chain([0.0]).chain(powers().map(f32::from_bits));
+        let keys = floats.map(|f| L32::from_f32_exact(f).unwrap().to_sortable_bits());
+        let mut prev = None;
+        for key in keys {
+            assert!(prev < Some(key));
+            prev = Some(key);
+        }
+
+        // The ordering keys are ordered like the values, and the next key is the next value.
+        let mut state = 0xBB67AE8584CAA73Bu64;
+        for _ in 0..100000 {
+            let a = L32(random_bits(&mut state));
+            let b = L32(random_bits(&mut state));
+            if [a, b].contains(&L32::NAR) {
+                continue;
+            }
+            let (x, y) = (a.explain().approx, b.explain().approx);
+            assert_eq!(
+                x.partial_cmp(&y),
+                Some(a.to_sortable_bits().cmp(&b.to_sortable_bits()))
+            );
+
+            let key = a.to_sortable_bits();
+            if key != u32::MAX {
+                let next = L32::from_sortable_bits(key + 1);
+                assert!(next.explain().approx > x, "{a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn metamorphic() {
+        // Exponents in [-16, 16), so products of three values and squares stay in range. This
+        // also excludes ZERO and NaR.
+        let mut state = 0x6A09E667F3BCC909u64;
+        let mut value = || {
+            let bits = random_bits(&mut state);
+            L32((bits & 0x80000000) | ((bits << 1) as i32 >> 4) as u32 & 0x7FFFFFFF)
+        };
+        for _ in 0..100000 {
+            let (a, b, c) = (value(), value(), value());
+
+            assert_eq!(a * b, b * a);
+            assert_eq!((a * b) * c, a * (b * c));
+            assert_eq!((a * b) / b, a);
+            assert_eq!(a / b, a * (L32::ONE / b));
+            assert_eq!(L32::ONE / (L32::ONE / a), a);
+            assert_eq!((a * a).sqrt(), a.abs());
+            assert_eq!(a.to_radians().to_degrees(), a);
+
+            let n = (c.0 >> 25) as i32 - 64;
+            assert_eq!(a << n, a * L32::from_parts(false, n as i8, 0));
+            assert_eq!((a << n) >> n, a);
+
+            // Rounded results are within one step.
+            let root = a.abs().sqrt();
+            assert!((root * root).raw_exp().abs_diff(a.abs().raw_exp()) <= 1);
+            assert_eq!(a.abs().midpoint(b.abs()), (a.abs() * b.abs()).sqrt());
+            let mean = crate::stats::geometric_mean(&[a.abs(), b.abs()]);
+            assert_eq!(mean, a.abs().midpoint(b.abs()));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn conformance() {
+        // Every operation must produce the same bits on every platform, so the hash of a long
+        // sequence of results is fixed. If this changes, the encoding or rounding of an
+        // operation changed.
+        let mut state = 0x2545F4914F6CDD1D;
+        let mut acc = L32::ONE;
+        // FNV-1a
+        let mut hash: u64 = 0xCBF29CE484222325;
+        for _ in 0..100_000 {
+            let x = L32(random_bits(&mut state));
+            let res = match random_bits(&mut state) % 3 {
+                0 => acc * x,
+                1 => acc / x,
+                _ => x.sqrt(),
+            };
+            for byte in res.to_bits().to_le_bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x100000001B3);
+            }
+
+            // Don't get stuck on ZERO or NaR.
+            acc = if res == L32::ZERO || res == L32::NAR {
+                x
+            } else {
+                res
+            };
+        }
+
+        assert_eq!(hash, 0x25C256373D691E22);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn conformance_ops() {
+        use crate::{
+            context::{Flags, MathContext, Rounding},
+            stats,
+        };
+
+        /// FNV-1a hash of the results of `op` for a pseudo-random sequence of operands.
+        fn hash(mut op: impl FnMut(&mut dyn FnMut() -> u32) -> u64) -> u64 {
+            let mut state = 0x2545F4914F6CDD1D;
+            let mut hash: u64 = 0xCBF29CE484222325;
+            for _ in 0..10_000 {
+                for byte in op(&mut || random_bits(&mut state)).to_le_bytes() {
+                    hash ^= u64::from(byte);
+                    hash = hash.wrapping_mul(0x100000001B3);
+                }
+            }
+            hash
+        }
+        fn bits(x: L32) -> u64 {
+            x.0.into()
+        }
+        /// The result of an operation in a context, with the raised flags in the high bits.
+        fn context(rounding: Rounding, next: &mut dyn FnMut() -> u32) -> u64 {
+            let mut ctx = MathContext::new(rounding);
+            let (a, b) = (L32(next()), L32(next()));
+            let res = match next() % 4 {
+                0 => ctx.mul(a, b),
+                1 => ctx.div(a, b),
+                2 => ctx.sqrt(a),
+                _ => ctx.midpoint(a, b),
+            };
+            let Flags {
+                overflow,
+                underflow,
+                nar,
+                inexact,
+            } = ctx.take_flags();
+            let flags = [overflow, underflow, nar, inexact]
+                .iter()
+                .rev()
+                .fold(0, |flags, &raised| flags << 1 | u64::from(raised));
+            bits(res) | flags << 32
+        }
+        // Small shifts, so the results don't just wrap around.
+        let shift = |next: &mut dyn FnMut() -> u32| next() as i32 >> 23;
+
+        let hashes = [
+            (
+                "midpoint",
+                hash(|next| bits(L32(next()).midpoint(L32(next())))),
+                0xA210A9A488C38F39,
+            ),
+            (
+                "mul_pow2",
+                hash(|next| bits(L32(next()).mul_pow2(next() as i32))),
+                0x3FA59A8EF05BED33,
+            ),
+            (
+                "shl",
+                hash(|next| bits(L32(next()) << shift(next))),
+                0xFB52BE306ED2844E,
+            ),
+            (
+                "shr",
+                hash(|next| bits(L32(next()) >> shift(next))),
+                0xBAE13C1D8DC36D03,
+            ),
+            (
+                "to_degrees",
+                hash(|next| bits(L32(next()).to_degrees())),
+                0x455E26F3C84CA94E,
+            ),
+            (
+                "to_radians",
+                hash(|next| bits(L32(next()).to_radians())),
+                0x6A6A70BFA8681F74,
+            ),
+            (
+                "NearestEven",
+                hash(|next| context(Rounding::NearestEven, next)),
+                0x56A069A88F2EAFDE,
+            ),
+            (
+                "TowardZero",
+                hash(|next| context(Rounding::TowardZero, next)),
+                0x9B6F92DED30CA7BA,
+            ),
+            (
+                "AwayFromZero",
+                hash(|next| context(Rounding::AwayFromZero, next)),
+                0x8F6965932BEEA4F3,
+            ),
+            (
+                "geometric_mean",
+                hash(|next| {
+                    // Positive values, the mean of anything else is NaR.
+                    let mut values = [L32::ONE; 16];
+                    let len = 1 + next() as usize % values.len();
+                    for x in &mut values[..len] {
+                        *x = L32(next() & 0x7FFFFFFF);
+                    }
+                    bits(stats::geometric_mean(&values[..len]))
+                }),
+                0xAAD6262BF291F04F,
+            ),
+        ];
+        for (op, hash, expected) in hashes {
+            assert_eq!(hash, expected, "{op}");
+        }
+    }
+}
+
+#[cfg(kani)]
+mod proofs {
+    use super::*;
+
+    fn any_l32() -> L32 {
+        L32(kani::any())
+    }
+
+    #[kani::proof]
+    fn mul_one_is_identity() {
+        let x = any_l32();
+        assert_eq!(x * L32::ONE, x);
+        assert_eq!(L32::ONE * x, x);
+    }
+
+    #[kani::proof]
+    fn mul_commutes() {
+        let a = any_l32();
+        let b = any_l32();
+        assert_eq!(a * b, b * a);
+    }
+
+    #[kani::proof]
+    fn mul_absorbs_zero_and_nar() {
+        let x = any_l32();
+        assert_eq!(x * L32::NAR, L32::NAR);
+        if x != L32::NAR {
+            assert_eq!(x * L32::ZERO, L32::ZERO);
+        }
+    }
+
+    #[kani::proof]
+    fn div_one_is_identity() {
+        let x = any_l32();
+        assert_eq!(x / L32::ONE, x);
+    }
+
+    #[kani::proof]
+    fn div_absorbs_zero_and_nar() {
+        let x = any_l32();
+        assert_eq!(x / L32::NAR, L32::NAR);
+        assert_eq!(L32::NAR / x, L32::NAR);
+        assert_eq!(x / L32::ZERO, L32::NAR);
+        if x != L32::NAR && x != L32::ZERO {
+            assert_eq!(L32::ZERO / x, L32::ZERO);
+        }
+    }
+
+    #[kani::proof]
+    fn div_inverts_mul() {
+        let a = any_l32();
+        let b = any_l32();
+        kani::assume(a != L32::ZERO && a != L32::NAR);
+        kani::assume(b != L32::ZERO && b != L32::NAR);
+        let prod = a * b;
+        kani::assume(prod != L32::ZERO && prod != L32::NAR);
+        assert_eq!(prod / b, a);
+    }
+
+    #[kani::proof]
+    fn sqrt_of_negative_is_nar() {
+        let x = any_l32();
+        if x.0 & 0x80000000 != 0 {
+            assert_eq!(x.sqrt(), L32::NAR);
+        } else {
+            assert_ne!(x.sqrt(), L32::NAR);
+        }
+    }
 }
@@ -1,5 +1,50 @@
 #![no_std]
 
+#[cfg(all(
+    feature = "no-panic",
+    any(feature = "panic-on-nar", feature = "trace-exceptions")
+))]
+compile_error!(
+    "the `no-panic` feature can't be combined with `panic-on-nar` or `trace-exceptions`, which \
+     panic or call user code from the checked operations"
+);
+
+#[cfg(all(
+    target_arch = "spirv",
+    any(
+        feature = "counters",
+        feature = "panic-on-nar",
+        feature = "trace-exceptions"
+    )
+))]
+compile_error!(
+    "`counters`, `panic-on-nar` and `trace-exceptions` need globals and formatting, which SPIR-V \
+     doesn't support"
+);
+
+pub mod context;
+pub mod error;
+pub mod exception;
+pub mod explain;
+pub mod golden;
+#[cfg(feature = "experimental")]
+pub mod gradual;
 mod l32;
+pub mod math;
+mod not_nar;
+#[cfg(feature = "reference")]
+pub mod reference;
+#[cfg(feature = "runtime-symbols")]
+pub mod runtime;
+pub mod shader;
+pub mod slice;
+pub mod solve;
+pub mod stats;
+#[cfg(feature = "experimental")]
+pub mod tapered;
+#[cfg(test)]
+pub(crate) mod test_util;
+#[cfg(feature = "wasm-bindgen")]
+mod wasm;
 
-pub use self::l32::L32;
+pub use self::{l32::L32, not_nar::NotNaR};
@@ -0,0 +1,98 @@
+//! Free functions shaped like the libm API, for mechanical ports from C and code generators.
+//!
+//! Each function forwards to the equivalent method or operator of [`L32`], which documents the
+//! behavior. Functions without an operator counterpart carry the `l32` suffix, like the `f`
+//! suffix of the f32 functions in libm.
+
+use crate::L32;
+
+/// `a * b`
+#[inline]
+pub fn mul(a: L32, b: L32) -> L32 {
+    a * b
+}
+
+/// `a / b`
+#[inline]
+pub fn div(a: L32, b: L32) -> L32 {
+    a / b
+}
+
+/// See [`L32::sqrt`].
+#[inline]
+pub fn sqrtl32(x: L32) -> L32 {
+    x.sqrt()
+}
+
+/// See [`L32::abs`].
+#[inline]
+pub fn fabsl32(x: L32) -> L32 {
+    x.abs()
+}
+
+/// See [`L32::copysign`].
+#[inline]
+pub fn copysignl32(x: L32, y: L32) -> L32 {
+    x.copysign(y)
+}
+
+/// `x * 2^n`, see [`L32::mul_pow2`].
+#[inline]
+pub fn ldexpl32(x: L32, n: i32) -> L32 {
+    x.mul_pow2(n)
+}
+
+/// See [`L32::frexp`], the residual is in `[1, 2)` rather than `[0.5, 1)`.
+#[inline]
+pub fn frexpl32(x: L32) -> (L32, i32) {
+    let (residual, exp) = x.frexp();
+    (residual, exp.into())
+}
+
+/// See [`L32::min`].
+#[inline]
+pub fn fminl32(a: L32, b: L32) -> L32 {
+    a.min(b)
+}
+
+/// See [`L32::max`].
+#[inline]
+pub fn fmaxl32(a: L32, b: L32) -> L32 {
+    a.max(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign() {
+        let x = L32::from_bits(0x00800000);
+        let neg = L32::from_bits(0x80800000);
+        assert_eq!(fabsl32(neg), x);
+        assert_eq!(fabsl32(x), x);
+        assert_eq!(fabsl32(L32::ZERO), L32::ZERO);
+        assert_eq!(fabsl32(L32::NAR), L32::NAR);
+
+        assert_eq!(copysignl32(x, neg), neg);
+        assert_eq!(copysignl32(neg, L32::ONE), x);
+        assert_eq!(copysignl32(x, L32::NAR), neg);
+        assert_eq!(copysignl32(L32::ZERO, neg), L32::ZERO);
+        assert_eq!(copysignl32(L32::NAR, x), L32::NAR);
+    }
+
+    #[test]
+    fn min_max() {
+        let x = L32::from_bits(0x00800000);
+        let neg = L32::from_bits(0x80800000);
+        assert_eq!(fminl32(x, neg), neg);
+        assert_eq!(fmaxl32(x, neg), x);
+        assert_eq!(fminl32(L32::NAR, x), x);
+        assert_eq!(fmaxl32(neg, L32::NAR), neg);
+        assert_eq!(fminl32(L32::NAR, L32::NAR), L32::NAR);
+        assert_eq!(
+            frexpl32(L32::from_bits(0x01C00000)),
+            (L32::from_bits(0x00400000), 3)
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{error::NarError, L32};
+use core::cmp::Ordering;
+
+/// An [`L32`] that is known not to be NaR.
+///
+/// Every value is then comparable, so this implements [`Ord`] and can be used as a key in
+/// ordered containers or in a priority queue. The order matches the numeric order.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NotNaR(L32);
+
+impl NotNaR {
+    /// Wraps a value, returning an error if it's NaR.
+    #[inline]
+    pub fn new(x: L32) -> Result<Self, NarError> {
+        if x == L32::NAR {
+            Err(NarError)
+        } else {
+            Ok(Self(x))
+        }
+    }
+
+    /// Returns the wrapped value.
+    #[inline]
+    pub const fn get(self) -> L32 {
+        self.0
+    }
+}
+
+impl From<NotNaR> for L32 {
+    #[inline]
+    fn from(x: NotNaR) -> Self {
+        x.0
+    }
+}
+
+impl TryFrom<L32> for NotNaR {
+    type Error = NarError;
+
+    #[inline]
+    fn try_from(x: L32) -> Result<Self, Self::Error> {
+        Self::new(x)
+    }
+}
+
+impl Ord for NotNaR {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_sortable_bits().cmp(&other.0.to_sortable_bits())
+    }
+}
+
+impl PartialOrd for NotNaR {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        extern crate std;
+        use std::collections::BTreeSet;
+
+        assert_eq!(NotNaR::new(L32::NAR), Err(NarError));
+        assert_eq!(NotNaR::try_from(L32::ZERO).map(NotNaR::get), Ok(L32::ZERO));
+
+        // In ascending numeric order.
+        let values = [
+            0xBFFFFFFF, 0x80000000, 0xC0000001, 0x40000000, 0x40000001, 0x00000000, 0x3FFFFFFF,
+        ]
+        .map(|bits| NotNaR::new(L32::from_bits(bits)).unwrap());
+        for pair in values.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+
+        let set: BTreeSet<_> = values.iter().rev().copied().collect();
+        assert!(set.into_iter().eq(values));
+        assert_eq!(values.iter().max(), Some(&values[6]));
+    }
+}
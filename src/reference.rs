@@ -0,0 +1,215 @@
+//! Slow reference implementations for differential testing.
+//!
+//! Every operation converts its operands to f64, computes the result with plain floating point
+//! math and converts back. This shares no code with the integer algorithms, which makes it an
+//! independent oracle. The results are within one step of the correctly rounded result, and
+//! usually equal to it, because f64 carries far more precision than the fraction of an L32.
+//!
+//! Unlike the real operations, results that are out of range give NaR instead of wrapping.
+
+use crate::L32;
+
+/// Converts to the nearest f64.
+///
+/// ZERO converts to 0.0 and NaR to NaN.
+pub fn to_f64(x: L32) -> f64 {
+    if x == L32::ZERO {
+        return 0.0;
+    }
+    if x == L32::NAR {
+        return f64::NAN;
+    }
+
+    let magnitude = libm::exp2(f64::from(x.raw_exp()) / f64::from(1 << L32::FRACTION_BITS));
+    if x.sign() {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Converts from f64, rounding the logarithm to nearest, ties to even.
+///
+/// Zeros convert to ZERO. NaN, infinities and magnitudes that are out of range give NaR.
+pub fn from_f64(x: f64) -> L32 {
+    if x == 0.0 {
+        return L32::ZERO;
+    }
+    if !x.is_finite() {
+        return L32::NAR;
+    }
+
+    let exp = libm::rint(libm::log2(libm::fabs(x)) * f64::from(1 << L32::FRACTION_BITS));
+    // The smallest exponent is the bit pattern of ZERO.
+    if exp <= -0x40000000 as f64 || exp >= 0x40000000 as f64 {
+        return L32::NAR;
+    }
+
+    let sign = if x < 0.0 { 0x80000000 } else { 0 };
+    L32::from_bits(sign | (exp as i32 as u32 & 0x7FFFFFFF))
+}
+
+/// Reference for [`L32::mul`](core::ops::Mul::mul).
+pub fn mul(a: L32, b: L32) -> L32 {
+    from_f64(to_f64(a) * to_f64(b))
+}
+
+/// Reference for [`L32::div`](core::ops::Div::div).
+pub fn div(a: L32, b: L32) -> L32 {
+    from_f64(to_f64(a) / to_f64(b))
+}
+
+/// Reference for [`L32::sqrt`].
+pub fn sqrt(x: L32) -> L32 {
+    from_f64(libm::sqrt(to_f64(x)))
+}
+
+/// Reference for [`L32::mul_pow2`].
+pub fn mul_pow2(x: L32, n: i32) -> L32 {
+    from_f64(to_f64(x) * libm::exp2(f64::from(n)))
+}
+
+/// Reference for [`L32::to_degrees`].
+pub fn to_degrees(x: L32) -> L32 {
+    from_f64(to_f64(x).to_degrees())
+}
+
+/// Reference for [`L32::to_radians`].
+pub fn to_radians(x: L32) -> L32 {
+    from_f64(to_f64(x).to_radians())
+}
+
+/// Reference for [`L32::midpoint`].
+pub fn midpoint(a: L32, b: L32) -> L32 {
+    let (a, b) = (to_f64(a), to_f64(b));
+    if a.is_sign_negative() != b.is_sign_negative() && a != 0.0 && b != 0.0 {
+        return L32::NAR;
+    }
+
+    from_f64(libm::copysign(libm::sqrt(a * b), a + b))
+}
+
+/// Reference for [`cumprod`](crate::slice::cumprod).
+///
+/// The running product is kept in f64, so it also goes out of range once it exceeds the range
+/// of f64.
+pub fn cumprod(values: &mut [L32]) {
+    let mut acc = 1.0;
+    for value in values.iter_mut() {
+        acc *= to_f64(*value);
+        *value = from_f64(acc);
+    }
+}
+
+/// Reference for [`geometric_mean`](crate::stats::geometric_mean).
+pub fn geometric_mean(values: &[L32]) -> L32 {
+    if values.is_empty() {
+        return L32::NAR;
+    }
+
+    let mut sum = 0.0;
+    let mut zero = false;
+    for &value in values {
+        let value = to_f64(value);
+        if value.is_nan() || value < 0.0 {
+            return L32::NAR;
+        }
+        zero |= value == 0.0;
+        sum += libm::log2(value);
+    }
+    if zero {
+        return L32::ZERO;
+    }
+
+    from_f64(libm::exp2(sum / values.len() as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{slice, stats, test_util::random_bits};
+
+    /// Random values with an exponent in `[-32, 32)`, so products stay in range.
+    fn values() -> impl Iterator<Item = L32> {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        core::iter::repeat_with(move || {
+            let bits = random_bits(&mut state);
+            let exp = ((bits << 1) as i32 >> 3) as u32 & 0x7FFFFFFF;
+            L32::from_bits((bits & 0x80000000) | exp)
+        })
+    }
+
+    /// Asserts that the results are at most one step apart.
+    fn assert_close(res: L32, expected: L32) {
+        let diff = res.to_sortable_bits().abs_diff(expected.to_sortable_bits());
+        assert!(diff <= 1, "{res:?} != {expected:?}");
+    }
+
+    #[test]
+    fn conversion() {
+        assert_eq!(to_f64(L32::ONE), 1.0);
+        assert_eq!(to_f64(L32::ZERO), 0.0);
+        assert!(to_f64(L32::NAR).is_nan());
+        assert_eq!(to_f64(L32::from_bits(0x80800000)), -2.0);
+        assert_eq!(from_f64(-0.25), L32::from_bits(0xFF000000));
+        assert_eq!(from_f64(-0.0), L32::ZERO);
+        assert_eq!(from_f64(f64::INFINITY), L32::NAR);
+        assert_eq!(from_f64(1e300), L32::NAR);
+        assert_eq!(from_f64(1e-300), L32::NAR);
+
+        for x in values().take(10000) {
+            assert_eq!(from_f64(to_f64(x)), x);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn differential() {
+        let special = [L32::ZERO, L32::NAR, L32::ONE, L32::from_bits(0x80000000)];
+        for &a in &special {
+            for &b in &special {
+                assert_eq!(a * b, mul(a, b));
+                assert_eq!(a / b, div(a, b));
+                assert_eq!(a.midpoint(b), midpoint(a, b));
+            }
+            assert_eq!(a.sqrt(), sqrt(a));
+            assert_eq!(a.mul_pow2(3), mul_pow2(a, 3));
+        }
+
+        let mut values = values();
+        for _ in 0..100000 {
+            let a = values.next().unwrap();
+            let b = values.next().unwrap();
+            assert_eq!(a * b, mul(a, b));
+            assert_eq!(a / b, div(a, b));
+            assert_close(a.sqrt(), sqrt(a));
+            assert_close(a.to_degrees(), to_degrees(a));
+            assert_close(a.to_radians(), to_radians(a));
+            assert_close(a.midpoint(b), midpoint(a, b));
+            let abs = [a, b].map(|x| L32::from_bits(x.to_bits() & 0x7FFFFFFF));
+            assert_close(stats::geometric_mean(&abs), geometric_mean(&abs));
+            let n = (b.to_bits() >> 27) as i32 - 16;
+            assert_eq!(a.mul_pow2(n), mul_pow2(a, n));
+        }
+
+        // Products of three values with exponents in [-32, 32) stay in range.
+        let mut res = [L32::ZERO; 3];
+        let mut expected = [L32::ZERO; 3];
+        for _ in 0..10000 {
+            for (res, expected) in res.iter_mut().zip(&mut expected) {
+                *res = values.next().unwrap();
+                *expected = *res;
+            }
+            slice::cumprod(&mut res);
+            cumprod(&mut expected);
+            for (&res, &expected) in res.iter().zip(&expected) {
+                assert_close(res, expected);
+            }
+        }
+        let mut res = [L32::ONE, L32::ZERO, L32::NAR, L32::ONE];
+        let mut expected = res;
+        slice::cumprod(&mut res);
+        cumprod(&mut expected);
+        assert_eq!(res, expected);
+    }
+}
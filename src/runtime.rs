@@ -0,0 +1,44 @@
+//! Runtime symbols for compilers and DSLs that lower LNS arithmetic to calls, like softfloat
+//! runtimes do for floats.
+//!
+//! Values are passed as their raw bits. The symbols are `__lns_<op>32`, in the style of the
+//! compiler-builtins float routines such as `__mulsf3`.
+
+use crate::L32;
+
+/// Multiplies two L32 values.
+#[no_mangle]
+pub extern "C" fn __lns_mul32(a: u32, b: u32) -> u32 {
+    (L32::from_bits(a) * L32::from_bits(b)).to_bits()
+}
+
+/// Divides two L32 values.
+#[no_mangle]
+pub extern "C" fn __lns_div32(a: u32, b: u32) -> u32 {
+    (L32::from_bits(a) / L32::from_bits(b)).to_bits()
+}
+
+/// Calculates the square root of an L32 value.
+#[no_mangle]
+pub extern "C" fn __lns_sqrt32(a: u32) -> u32 {
+    L32::from_bits(a).sqrt().to_bits()
+}
+
+/// Multiplies an L32 value by `2^n`.
+#[no_mangle]
+pub extern "C" fn __lns_mulpow2_32(a: u32, n: i32) -> u32 {
+    L32::from_bits(a).mul_pow2(n).to_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbols() {
+        assert_eq!(__lns_mul32(0x00800000, 0x00800000), 0x01000000);
+        assert_eq!(__lns_div32(0x00800000, 0x01000000), 0x7F800000);
+        assert_eq!(__lns_sqrt32(0x01000000), 0x00800000);
+        assert_eq!(__lns_mulpow2_32(0x00800000, -1), 0x00000000);
+    }
+}
@@ -0,0 +1,449 @@
+//! Shader source implementing the operations on `u32` bits.
+//!
+//! The functions are transliterations of the [`golden`](crate::golden) model and give the same
+//! bits, which the tests check by interpreting the sources as parsed by naga. This lets data stay
+//! in LNS between CPU and GPU compute. Values are passed around as their
+//! raw bits. Include the source for your shading language in a shader module, e.g. by
+//! prepending it to your own source.
+//!
+//! Every function is named after the golden one without the `_bits` suffix: `l32_mul`,
+//! `l32_div`, `l32_sqrt` and `l32_midpoint`. The constants `L32_ZERO`, `L32_NAR` and `L32_ONE`
+//! are defined as well.
+
+/// Functions for WGSL.
+pub const WGSL: &str = r#"
+const L32_ZERO: u32 = 0x40000000u;
+const L32_NAR: u32 = 0xC0000000u;
+const L32_ONE: u32 = 0u;
+
+fn l32_exp(a: u32) -> i32 {
+    return bitcast<i32>(a << 1u) >> 1u;
+}
+
+fn l32_mul(a: u32, b: u32) -> u32 {
+    var res = ((a ^ b) & 0x80000000u) | ((a + b) & 0x7FFFFFFFu);
+    res = select(res, L32_ZERO, a == L32_ZERO || b == L32_ZERO);
+    res = select(res, L32_NAR, a == L32_NAR || b == L32_NAR);
+    return res;
+}
+
+fn l32_div(a: u32, b: u32) -> u32 {
+    var res = ((a ^ b) & 0x80000000u) | ((a - b) & 0x7FFFFFFFu);
+    res = select(res, L32_ZERO, a == L32_ZERO);
+    res = select(res, L32_NAR, a == L32_NAR || b == L32_NAR || b == L32_ZERO);
+    return res;
+}
+
+fn l32_sqrt(a: u32) -> u32 {
+    let e = l32_exp(a);
+    var res = bitcast<u32>((e >> 1u) + (e & (e >> 1u) & 1)) & 0x7FFFFFFFu;
+    res = select(res, L32_ZERO, a == L32_ZERO);
+    res = select(res, L32_NAR, (a & 0x80000000u) != 0u);
+    return res;
+}
+
+fn l32_midpoint(a: u32, b: u32) -> u32 {
+    let sum = l32_exp(a) + l32_exp(b);
+    let e = (sum >> 1u) + (sum & (sum >> 1u) & 1);
+    var res = (a & 0x80000000u) | (bitcast<u32>(e) & 0x7FFFFFFFu);
+    res = select(res, L32_NAR, ((a ^ b) & 0x80000000u) != 0u);
+    res = select(res, L32_ZERO, a == L32_ZERO || b == L32_ZERO);
+    res = select(res, L32_NAR, a == L32_NAR || b == L32_NAR);
+    return res;
+}
+"#;
+
+/// Functions for GLSL, version 3.00 ES, 4.00 and later.
+pub const GLSL: &str = r#"
+const uint L32_ZERO = 0x40000000u;
+const uint L32_NAR = 0xC0000000u;
+const uint L32_ONE = 0u;
+
+int l32_exp(uint a) {
+    return int(a << 1) >> 1;
+}
+
+uint l32_mul(uint a, uint b) {
+    uint res = ((a ^ b) & 0x80000000u) | ((a + b) & 0x7FFFFFFFu);
+    res = (a == L32_ZERO || b == L32_ZERO) ? L32_ZERO : res;
+    res = (a == L32_NAR || b == L32_NAR) ? L32_NAR : res;
+    return res;
+}
+
+uint l32_div(uint a, uint b) {
+    uint res = ((a ^ b) & 0x80000000u) | ((a - b) & 0x7FFFFFFFu);
+    res = (a == L32_ZERO) ? L32_ZERO : res;
+    res = (a == L32_NAR || b == L32_NAR || b == L32_ZERO) ? L32_NAR : res;
+    return res;
+}
+
+uint l32_sqrt(uint a) {
+    int e = l32_exp(a);
+    uint res = uint((e >> 1) + (e & (e >> 1) & 1)) & 0x7FFFFFFFu;
+    res = (a == L32_ZERO) ? L32_ZERO : res;
+    res = ((a & 0x80000000u) != 0u) ? L32_NAR : res;
+    return res;
+}
+
+uint l32_midpoint(uint a, uint b) {
+    int sum = l32_exp(a) + l32_exp(b);
+    int e = (sum >> 1) + (sum & (sum >> 1) & 1);
+    uint res = (a & 0x80000000u) | (uint(e) & 0x7FFFFFFFu);
+    res = (((a ^ b) & 0x80000000u) != 0u) ? L32_NAR : res;
+    res = (a == L32_ZERO || b == L32_ZERO) ? L32_ZERO : res;
+    res = (a == L32_NAR || b == L32_NAR) ? L32_NAR : res;
+    return res;
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::test_util::random_bits;
+    use crate::{golden, L32};
+    use naga::{
+        valid::{Capabilities, ValidationFlags, Validator},
+        BinaryOperator, Expression, Function, Handle, Literal, Module, ScalarKind, Statement,
+        UnaryOperator,
+    };
+    use std::{format, vec, vec::Vec};
+
+    fn validate(module: &naga::Module) {
+        Validator::new(ValidationFlags::all(), Capabilities::empty())
+            .validate(module)
+            .unwrap();
+    }
+
+    #[test]
+    fn wgsl_is_valid() {
+        let module = naga::front::wgsl::parse_str(WGSL).unwrap();
+        validate(&module);
+        for name in ["l32_mul", "l32_div", "l32_sqrt", "l32_midpoint"] {
+            assert!(module
+                .functions
+                .iter()
+                .any(|(_, f)| f.name.as_deref() == Some(name)));
+        }
+    }
+
+    fn glsl_module() -> naga::Module {
+        // Functions only end up in the module if an entry point uses them.
+        let source = format!(
+            "#version 450\n{GLSL}\nlayout(local_size_x = 1) in;\nvoid main() {{\n    \
+             uint x = l32_mul(L32_ONE, l32_div(L32_ZERO, l32_sqrt(l32_midpoint(L32_NAR, 1u))));\n\
+             }}\n"
+        );
+        let options = naga::front::glsl::Options::from(naga::ShaderStage::Compute);
+        naga::front::glsl::Frontend::default()
+            .parse(&options, &source)
+            .unwrap()
+    }
+
+    #[test]
+    fn glsl_is_valid() {
+        validate(&glsl_module());
+    }
+
+    /// Parses an integer literal like `0x80000000u` or `0u`.
+    fn literal(s: &str) -> u32 {
+        let s = s.trim_end_matches('u');
+        match s.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16).unwrap(),
+            None => s.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn constants_match_golden() {
+        for source in [WGSL, GLSL] {
+            for (name, value) in [("ZERO", L32::ZERO), ("NAR", L32::NAR), ("ONE", L32::ONE)] {
+                let line = source
+                    .lines()
+                    .find(|l| l.starts_with("const") && l.contains(&format!("L32_{name}")))
+                    .unwrap();
+                let (_, bits) = line.trim_end_matches(';').rsplit_once(' ').unwrap();
+                assert_eq!(literal(bits), value.to_bits(), "{line}");
+            }
+
+            // The only other constants are the sign and logarithm masks.
+            let masks = [
+                0x80000000,
+                0x7FFFFFFF,
+                L32::ZERO.to_bits(),
+                L32::NAR.to_bits(),
+            ];
+            for word in source.split([' ', '(', ')', ';']) {
+                if word.starts_with("0x") {
+                    assert!(masks.contains(&literal(word)), "{word}");
+                }
+            }
+        }
+
+        // The sqrt and midpoint rounding is the ties to even expression of the golden model.
+        let ties_to_even =
+            |e: &str, one: &str| format!("({e} >> {one}) + ({e} & ({e} >> {one}) & 1)");
+        for (source, one) in [(WGSL, "1u"), (GLSL, "1")] {
+            assert_eq!(source.matches(&ties_to_even("e", one)).count(), 1);
+            assert_eq!(source.matches(&ties_to_even("sum", one)).count(), 1);
+        }
+        assert_eq!(golden::l32_sqrt_bits(0x00000001), 0x00000000);
+        assert_eq!(golden::l32_sqrt_bits(0x00000003), 0x00000002);
+    }
+
+    /// A value in the shader functions, which only use `u32`, `i32` and `bool` scalars.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Value {
+        Bool(bool),
+        U32(u32),
+        I32(i32),
+    }
+
+    impl Value {
+        fn bits(self) -> u32 {
+            match self {
+                Self::Bool(b) => b.into(),
+                Self::U32(x) => x,
+                Self::I32(x) => x as u32,
+            }
+        }
+
+        fn with_bits(self, bits: u32) -> Self {
+            match self {
+                Self::Bool(_) => Self::Bool(bits != 0),
+                Self::U32(_) => Self::U32(bits),
+                Self::I32(_) => Self::I32(bits as i32),
+            }
+        }
+    }
+
+    fn literal_value(literal: &Literal) -> Value {
+        match *literal {
+            Literal::Bool(b) => Value::Bool(b),
+            Literal::U32(x) => Value::U32(x),
+            Literal::I32(x) => Value::I32(x),
+            ref literal => panic!("unsupported literal {literal:?}"),
+        }
+    }
+
+    /// Interpreter for the subset of naga IR the shader functions are parsed into.
+    ///
+    /// Both shading languages go through the same IR, so this checks the semantics of the
+    /// sources as the frontends see them, not a transliteration.
+    struct Frame<'a> {
+        module: &'a Module,
+        function: &'a Function,
+        arguments: &'a [Value],
+        values: Vec<Option<Value>>,
+        locals: Vec<Option<Value>>,
+    }
+
+    impl<'a> Frame<'a> {
+        fn call(module: &'a Module, name: &str, arguments: &'a [Value]) -> u32 {
+            let (_, function) = module
+                .functions
+                .iter()
+                .find(|(_, f)| f.name.as_deref() == Some(name))
+                .unwrap();
+            Self::new(module, function, arguments).run().bits()
+        }
+
+        fn new(module: &'a Module, function: &'a Function, arguments: &'a [Value]) -> Self {
+            let mut frame = Self {
+                module,
+                function,
+                arguments,
+                values: vec![None; function.expressions.len()],
+                locals: vec![None; function.local_variables.len()],
+            };
+            for (handle, local) in function.local_variables.iter() {
+                frame.locals[handle.index()] = local.init.map(|init| frame.eval(init));
+            }
+            frame
+        }
+
+        fn run(&mut self) -> Value {
+            self.exec(&self.function.body).expect("no return")
+        }
+
+        fn local(&self, pointer: Handle<Expression>) -> usize {
+            match self.function.expressions[pointer] {
+                Expression::LocalVariable(local) => local.index(),
+                ref e => panic!("unsupported pointer {e:?}"),
+            }
+        }
+
+        /// Executes a block, returning the returned value if there is one.
+        fn exec(&mut self, block: &naga::Block) -> Option<Value> {
+            for statement in block.iter() {
+                match *statement {
+                    Statement::Emit(ref range) => {
+                        for expr in range.clone() {
+                            self.values[expr.index()] = Some(self.eval(expr));
+                        }
+                    }
+                    Statement::Block(ref block) => {
+                        if let Some(value) = self.exec(block) {
+                            return Some(value);
+                        }
+                    }
+                    Statement::If {
+                        condition,
+                        ref accept,
+                        ref reject,
+                    } => {
+                        let block = match self.eval(condition) {
+                            Value::Bool(true) => accept,
+                            Value::Bool(false) => reject,
+                            value => panic!("condition {value:?}"),
+                        };
+                        if let Some(value) = self.exec(block) {
+                            return Some(value);
+                        }
+                    }
+                    Statement::Store { pointer, value } => {
+                        let (local, value) = (self.local(pointer), self.eval(value));
+                        self.locals[local] = Some(value);
+                    }
+                    Statement::Call {
+                        function,
+                        ref arguments,
+                        result,
+                    } => {
+                        let arguments: Vec<_> = arguments.iter().map(|&a| self.eval(a)).collect();
+                        let function = &self.module.functions[function];
+                        let value = Frame::new(self.module, function, &arguments).run();
+                        if let Some(result) = result {
+                            self.values[result.index()] = Some(value);
+                        }
+                    }
+                    Statement::Return { value } => return Some(self.eval(value.unwrap())),
+                    ref statement => panic!("unsupported statement {statement:?}"),
+                }
+            }
+            None
+        }
+
+        fn eval(&self, expr: Handle<Expression>) -> Value {
+            if let Some(value) = self.values[expr.index()] {
+                return value;
+            }
+            match self.function.expressions[expr] {
+                Expression::FunctionArgument(i) => self.arguments[i as usize],
+                Expression::Literal(ref literal) => literal_value(literal),
+                Expression::Constant(constant) => {
+                    let init = self.module.constants[constant].init;
+                    match self.module.global_expressions[init] {
+                        Expression::Literal(ref literal) => literal_value(literal),
+                        ref e => panic!("unsupported constant {e:?}"),
+                    }
+                }
+                Expression::Load { pointer } => {
+                    self.locals[self.local(pointer)].expect("uninitialized local")
+                }
+                Expression::Unary { op, expr } => match (op, self.eval(expr)) {
+                    (UnaryOperator::LogicalNot, Value::Bool(b)) => Value::Bool(!b),
+                    (UnaryOperator::BitwiseNot, x) => x.with_bits(!x.bits()),
+                    (op, x) => panic!("unsupported {op:?} {x:?}"),
+                },
+                Expression::Binary { op, left, right } => {
+                    binary(op, self.eval(left), self.eval(right))
+                }
+                Expression::Select {
+                    condition,
+                    accept,
+                    reject,
+                } => match self.eval(condition) {
+                    Value::Bool(true) => self.eval(accept),
+                    Value::Bool(false) => self.eval(reject),
+                    value => panic!("condition {value:?}"),
+                },
+                // Bitcasts and conversions between 32 bit integers both keep the bits.
+                Expression::As { expr, kind, .. } => {
+                    let bits = self.eval(expr).bits();
+                    match kind {
+                        ScalarKind::Uint => Value::U32(bits),
+                        ScalarKind::Sint => Value::I32(bits as i32),
+                        kind => panic!("unsupported cast to {kind:?}"),
+                    }
+                }
+                ref e => panic!("unsupported expression {e:?}"),
+            }
+        }
+    }
+
+    fn binary(op: BinaryOperator, left: Value, right: Value) -> Value {
+        use BinaryOperator as Op;
+
+        let (a, b) = (left.bits(), right.bits());
+        match (op, left) {
+            (Op::Equal, _) => Value::Bool(a == b),
+            (Op::NotEqual, _) => Value::Bool(a != b),
+            (Op::LogicalAnd, Value::Bool(_)) => Value::Bool(a & b != 0),
+            (Op::LogicalOr, Value::Bool(_)) => Value::Bool(a | b != 0),
+            (_, Value::Bool(_)) => panic!("unsupported {op:?} on bool"),
+            (Op::Add, _) => left.with_bits(a.wrapping_add(b)),
+            (Op::Subtract, _) => left.with_bits(a.wrapping_sub(b)),
+            (Op::And, _) => left.with_bits(a & b),
+            (Op::InclusiveOr, _) => left.with_bits(a | b),
+            (Op::ExclusiveOr, _) => left.with_bits(a ^ b),
+            (Op::ShiftLeft, _) => left.with_bits(a << b),
+            (Op::ShiftRight, Value::I32(x)) => Value::I32(x >> b),
+            (Op::ShiftRight, _) => left.with_bits(a >> b),
+            (op, _) => panic!("unsupported {op:?}"),
+        }
+    }
+
+    #[test]
+    fn evaluates_like_golden() {
+        let modules = [
+            ("WGSL", naga::front::wgsl::parse_str(WGSL).unwrap()),
+            ("GLSL", glsl_module()),
+        ];
+        let special = [
+            L32::ZERO.to_bits(),
+            L32::NAR.to_bits(),
+            L32::ONE.to_bits(),
+            0x80000000,
+            0x3FFFFFFF,
+            0x40000001,
+            0xBFFFFFFF,
+            0xC0000001,
+            0x00000001,
+            0x00000003,
+            0x7FFFFFFF,
+            0xFFFFFFFF,
+        ];
+        let mut state = 0x2545F4914F6CDD1D;
+        let random: Vec<_> = (0..2000).map(|_| random_bits(&mut state)).collect();
+        let pairs = special
+            .iter()
+            .flat_map(|&a| special.iter().map(move |&b| (a, b)))
+            .chain(random.chunks(2).map(|c| (c[0], c[1])));
+
+        for (language, module) in &modules {
+            let unary = |name, a| Frame::call(module, name, &[Value::U32(a)]);
+            let binary = |name, a, b| Frame::call(module, name, &[Value::U32(a), Value::U32(b)]);
+            for (a, b) in pairs.clone() {
+                let msg = format!("{language} {a:#010X} {b:#010X}");
+                assert_eq!(
+                    binary("l32_mul", a, b),
+                    golden::l32_mul_bits(a, b),
+                    "mul {msg}"
+                );
+                assert_eq!(
+                    binary("l32_div", a, b),
+                    golden::l32_div_bits(a, b),
+                    "div {msg}"
+                );
+                assert_eq!(
+                    binary("l32_midpoint", a, b),
+                    golden::l32_midpoint_bits(a, b),
+                    "midpoint {msg}"
+                );
+                assert_eq!(unary("l32_sqrt", a), golden::l32_sqrt_bits(a), "sqrt {msg}");
+            }
+        }
+    }
+}
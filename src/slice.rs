@@ -0,0 +1,293 @@
+//! Operations on slices of values.
+//!
+//! Fixed size arrays coerce to slices, so `&[L32; N]` and `&mut [L32; N]` can be passed to all of
+//! these directly, without allocating.
+
+use crate::L32;
+
+/// How reductions treat NaR elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NarPolicy {
+    /// NaR elements are ignored.
+    Skip,
+    /// The result is NaR if any element is NaR.
+    Propagate,
+}
+
+/// Sorts the slice in ascending order, with NaR first.
+///
+/// This is an LSD radix sort over [`L32::to_sortable_bits`], which is considerably faster than
+/// a comparison sort for large slices. `scratch` is used as temporary storage, its contents
+/// afterwards are unspecified.
+///
+/// # Panics
+/// Panics if `scratch` is shorter than `values`.
+pub fn radix_sort(values: &mut [L32], scratch: &mut [L32]) {
+    let len = values.len();
+    let scratch = &mut scratch[..len];
+
+    let mut counts = [[0; 256]; 4];
+    for value in values.iter() {
+        let key = value.to_sortable_bits();
+        for (i, count) in counts.iter_mut().enumerate() {
+            count[(key >> (i * 8)) as usize & 0xFF] += 1;
+        }
+    }
+
+    let mut src = values;
+    let mut dst = scratch;
+    let mut swapped = false;
+    for (i, count) in counts.iter().enumerate() {
+        // Nothing to do if every key has the same digit.
+        if count.contains(&len) {
+            continue;
+        }
+
+        let mut offsets = [0; 256];
+        let mut sum = 0;
+        for (offset, &count) in offsets.iter_mut().zip(count) {
+            *offset = sum;
+            sum += count;
+        }
+
+        for &value in src.iter() {
+            let digit = (value.to_sortable_bits() >> (i * 8)) as usize & 0xFF;
+            dst[offsets[digit]] = value;
+            offsets[digit] += 1;
+        }
+
+        core::mem::swap(&mut src, &mut dst);
+        swapped = !swapped;
+    }
+
+    if swapped {
+        dst.copy_from_slice(src);
+    }
+}
+
+/// Returns the smallest value.
+///
+/// Returns NaR if the slice is empty or, when skipping NaR, only contains NaR.
+pub fn min(values: &[L32], nar: NarPolicy) -> L32 {
+    let keys = values.iter().map(|v| v.to_sortable_bits());
+    match nar {
+        // NaR sorts first, wrapping it around makes it lose against everything else.
+        NarPolicy::Skip => {
+            let key = keys.map(|k| k.wrapping_sub(1)).min().unwrap_or(u32::MAX);
+            L32::from_sortable_bits(key.wrapping_add(1))
+        }
+        NarPolicy::Propagate => keys.min().map_or(L32::NAR, L32::from_sortable_bits),
+    }
+}
+
+/// Returns the largest value.
+///
+/// Returns NaR if the slice is empty or, when skipping NaR, only contains NaR.
+pub fn max(values: &[L32], nar: NarPolicy) -> L32 {
+    let keys = values.iter().map(|v| v.to_sortable_bits());
+    match nar {
+        // NaR sorts first, so it only wins if there is nothing else.
+        NarPolicy::Skip => L32::from_sortable_bits(keys.max().unwrap_or(0)),
+        NarPolicy::Propagate => keys
+            .map(|k| k.wrapping_sub(1))
+            .max()
+            .map_or(L32::NAR, |k| L32::from_sortable_bits(k.wrapping_add(1))),
+    }
+}
+
+/// Returns the index of the first occurrence of the smallest value.
+///
+/// When propagating NaR, this is the index of the first NaR if there is one. Returns `None` if
+/// the slice is empty or, when skipping NaR, only contains NaR.
+pub fn argmin(values: &[L32], nar: NarPolicy) -> Option<usize> {
+    let keys = values.iter().map(|v| v.to_sortable_bits()).enumerate();
+    let res = match nar {
+        NarPolicy::Skip => keys.filter(|&(_, k)| k != 0).min_by_key(|&(_, k)| k),
+        NarPolicy::Propagate => keys.min_by_key(|&(_, k)| k),
+    };
+    res.map(|(i, _)| i)
+}
+
+/// Returns the index of the first occurrence of the largest value.
+///
+/// When propagating NaR, this is the index of the first NaR if there is one. Returns `None` if
+/// the slice is empty or, when skipping NaR, only contains NaR.
+pub fn argmax(values: &[L32], nar: NarPolicy) -> Option<usize> {
+    let keys = values.iter().map(|v| v.to_sortable_bits()).enumerate();
+    // `min_by_key` returns the first of equal elements, `max_by_key` the last.
+    let res = match nar {
+        NarPolicy::Skip => keys.filter(|&(_, k)| k != 0).min_by_key(|&(_, k)| !k),
+        NarPolicy::Propagate => keys.min_by_key(|&(_, k)| !k.wrapping_sub(1)),
+    };
+    res.map(|(i, _)| i)
+}
+
+/// Replaces every value with the product of it and all values before it.
+///
+/// Multiplication is exact, so unlike a floating point scan no error accumulates. Like with
+/// multiplication, the exponent wraps around if a product is out of range.
+pub fn cumprod(values: &mut [L32]) {
+    let mut acc = L32::ONE;
+    for value in values.iter_mut() {
+        acc *= *value;
+        *value = acc;
+    }
+}
+
+/// Iterator version of [`cumprod`], yielding the running product after each value.
+pub fn cumprod_iter<I: IntoIterator<Item = L32>>(values: I) -> impl Iterator<Item = L32> {
+    values.into_iter().scan(L32::ONE, |acc, value| {
+        *acc *= value;
+        Some(*acc)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_bits;
+
+    /// Fills the start of `buf` with values from `bits` and returns that part.
+    fn from_bits<'a>(buf: &'a mut [L32], bits: &[u32]) -> &'a mut [L32] {
+        let buf = &mut buf[..bits.len()];
+        for (value, &bits) in buf.iter_mut().zip(bits) {
+            *value = L32::from_bits(bits);
+        }
+        buf
+    }
+
+    #[test]
+    fn radix_sort() {
+        fn test(bits: &[u32]) {
+            let mut values = [L32::ZERO; 64];
+            let mut scratch = [L32::ZERO; 64];
+            let values = from_bits(&mut values, bits);
+
+            let mut expected = [L32::ZERO; 64];
+            let expected = &mut expected[..bits.len()];
+            expected.copy_from_slice(values);
+            expected.sort_unstable_by_key(|v| v.to_sortable_bits());
+
+            super::radix_sort(values, &mut scratch);
+            assert_eq!(values, expected);
+        }
+
+        test(&[]);
+        test(&[0xDEADBEEF]);
+        test(&[0x40000000, 0x40000000, 0x40000000]);
+        test(&[0x00000001, 0x00000000]);
+        test(&[0x00000100, 0x00000000]);
+        test(&[0x00010000, 0x00000000]);
+        test(&[0x01000000, 0x00000000]);
+        test(&[
+            0x3FFFFFFF, 0xC0000000, 0x00000000, 0x80000000, 0x40000000, 0xBFFFFFFF, 0x7FFFFFFF,
+            0xFFFFFFFF, 0xC0000001, 0x40000001, 0xDEADBEEF, 0xBEEFDEAD, 0x12345678, 0x00000000,
+        ]);
+
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut bits = [0; 64];
+        for bits in bits.iter_mut() {
+            *bits = random_bits(&mut state);
+        }
+        test(&bits);
+    }
+
+    #[test]
+    fn min_max() {
+        fn test(bits: &[u32], skip: (u32, u32), propagate: (u32, u32)) {
+            let mut values = [L32::ZERO; 8];
+            let values = from_bits(&mut values, bits);
+
+            let min = super::min(values, NarPolicy::Skip);
+            let max = super::max(values, NarPolicy::Skip);
+            assert_eq!((min.to_bits(), max.to_bits()), skip);
+            let min = super::min(values, NarPolicy::Propagate);
+            let max = super::max(values, NarPolicy::Propagate);
+            assert_eq!((min.to_bits(), max.to_bits()), propagate);
+        }
+
+        let nar = (0xC0000000, 0xC0000000);
+        test(&[], nar, nar);
+        test(&[0xC0000000, 0xC0000000], nar, nar);
+        test(&[0x00000000], (0, 0), (0, 0));
+        test(
+            &[0x3FFFFFFF, 0xBFFFFFFF],
+            (0xBFFFFFFF, 0x3FFFFFFF),
+            (0xBFFFFFFF, 0x3FFFFFFF),
+        );
+        test(
+            &[0x40000000, 0xC0000001],
+            (0xC0000001, 0x40000000),
+            (0xC0000001, 0x40000000),
+        );
+        test(
+            &[0x00800000, 0xC0000000, 0x7FFFFFFF, 0x80000000],
+            (0x80000000, 0x00800000),
+            nar,
+        );
+        test(&[0xC0000000, 0x3FFFFFFF], (0x3FFFFFFF, 0x3FFFFFFF), nar);
+        test(&[0xBFFFFFFF, 0xC0000000], (0xBFFFFFFF, 0xBFFFFFFF), nar);
+    }
+
+    #[test]
+    fn argmin_argmax() {
+        fn test(bits: &[u32], skip: Option<(usize, usize)>, propagate: Option<(usize, usize)>) {
+            let mut values = [L32::ZERO; 8];
+            let values = from_bits(&mut values, bits);
+
+            let min = super::argmin(values, NarPolicy::Skip);
+            let max = super::argmax(values, NarPolicy::Skip);
+            assert_eq!(min.zip(max), skip);
+            let min = super::argmin(values, NarPolicy::Propagate);
+            let max = super::argmax(values, NarPolicy::Propagate);
+            assert_eq!(min.zip(max), propagate);
+        }
+
+        test(&[], None, None);
+        test(&[0xC0000000], None, Some((0, 0)));
+        test(&[0x00000000], Some((0, 0)), Some((0, 0)));
+        test(&[0x00000000, 0x00000000], Some((0, 0)), Some((0, 0)));
+        test(&[0x3FFFFFFF, 0xBFFFFFFF], Some((1, 0)), Some((1, 0)));
+        test(
+            &[
+                0x40000000, 0x3FFFFFFF, 0xC0000000, 0x80000000, 0x80000000, 0xC0000000,
+            ],
+            Some((3, 1)),
+            Some((2, 2)),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn cumprod() {
+        fn test(bits: &[u32], expected: &[u32]) {
+            let mut values = [L32::ZERO; 8];
+            let values = from_bits(&mut values, bits);
+            let mut buf = [L32::ZERO; 8];
+            let expected = from_bits(&mut buf, expected);
+
+            let iter = super::cumprod_iter(values.iter().copied());
+            assert!(iter.eq(expected.iter().copied()));
+            super::cumprod(values);
+            assert_eq!(values, expected);
+        }
+
+        test(&[], &[]);
+        test(&[0x00800000], &[0x00800000]);
+        test(
+            &[0x00800000, 0x80800000, 0x7F800000, 0x00000001],
+            &[0x00800000, 0x81000000, 0x80800000, 0x80800001],
+        );
+        test(
+            &[0x00800000, 0x40000000, 0x00800000, 0xC0000000],
+            &[0x00800000, 0x40000000, 0x40000000, 0xC0000000],
+        );
+        test(&[0xC0000000, 0x40000000], &[0xC0000000, 0xC0000000]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn radix_sort_short_scratch() {
+        super::radix_sort(&mut [L32::ONE; 4], &mut [L32::ONE; 3]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Root finding.
+
+use crate::L32;
+
+/// Finds a root of `f` between `a` and `b` by bisection.
+///
+/// `f(a)` and `f(b)` must have opposite signs, or either must be ZERO. The interval is halved
+/// in the number of representable values rather than in distance, see
+/// [`L32::to_sortable_bits`], so this terminates after at most 32 steps with an exact root or
+/// two adjacent values around it. Of those, the one where `f` has the smaller magnitude is
+/// returned.
+///
+/// Returns `None` if the signs are the same, or if either bound or any value of `f` is NaR.
+pub fn bisect(mut f: impl FnMut(L32) -> L32, a: L32, b: L32) -> Option<L32> {
+    let (mut lo, mut hi) = (a.to_sortable_bits(), b.to_sortable_bits());
+    if lo > hi {
+        core::mem::swap(&mut lo, &mut hi);
+    }
+    if lo == 0 {
+        return None;
+    }
+
+    let mut f_lo = f(L32::from_sortable_bits(lo));
+    let mut f_hi = f(L32::from_sortable_bits(hi));
+    if f_lo == L32::NAR || f_hi == L32::NAR {
+        return None;
+    }
+    if f_lo == L32::ZERO {
+        return Some(L32::from_sortable_bits(lo));
+    }
+    if f_hi == L32::ZERO {
+        return Some(L32::from_sortable_bits(hi));
+    }
+    if f_lo.sign() == f_hi.sign() {
+        return None;
+    }
+
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        let x = L32::from_sortable_bits(mid);
+        let f_mid = f(x);
+        if f_mid == L32::NAR {
+            return None;
+        }
+        if f_mid == L32::ZERO {
+            return Some(x);
+        }
+        if f_mid.sign() == f_lo.sign() {
+            (lo, f_lo) = (mid, f_mid);
+        } else {
+            (hi, f_hi) = (mid, f_mid);
+        }
+    }
+
+    // The magnitude is ordered like the exponent.
+    let res = if f_lo.raw_exp() <= f_hi.raw_exp() {
+        lo
+    } else {
+        hi
+    };
+    Some(L32::from_sortable_bits(res))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ZERO if `x^n == c`, `x^n / c` if that's larger than 1, otherwise `-(c / x^n)`.
+    fn ratio(x: L32, n: u32, c: L32) -> L32 {
+        let pow = (1..n).fold(x, |acc, _| acc * x);
+        let above = pow / c;
+        if pow == c {
+            L32::ZERO
+        } else if above.to_sortable_bits() > L32::ONE.to_sortable_bits() {
+            above
+        } else {
+            L32::from_bits((c / pow).to_bits() | 0x80000000)
+        }
+    }
+
+    #[test]
+    fn bisect() {
+        let two = L32::from_bits(0x00800000);
+        let four = L32::from_bits(0x01000000);
+
+        // sqrt(2) is exact.
+        let root = super::bisect(|x| ratio(x, 2, two), L32::ONE, four);
+        assert_eq!(root, Some(L32::from_bits(0x00400000)));
+        // Bounds in either order, and exact roots at the bounds.
+        let root = super::bisect(|x| ratio(x, 2, two), four, L32::ONE);
+        assert_eq!(root, Some(L32::from_bits(0x00400000)));
+        assert_eq!(super::bisect(|x| ratio(x, 2, four), two, four), Some(two));
+        assert_eq!(super::bisect(|x| ratio(x, 1, four), two, four), Some(four));
+
+        // The cube root of 2 lies between 0x2AAAAA and 0x2AAAAB, closer to the latter.
+        let root = super::bisect(|x| ratio(x, 3, two), L32::ONE, two);
+        assert_eq!(root, Some(L32::from_bits(0x002AAAAB)));
+
+        // Across ZERO and the sign change.
+        let root = super::bisect(|x| x, L32::from_bits(0x80800000), two);
+        assert_eq!(root, Some(L32::ZERO));
+        let root = super::bisect(|x| x * x * x, L32::from_bits(0x80800000), two);
+        assert_eq!(root, Some(L32::ZERO));
+
+        assert_eq!(super::bisect(|x| x, two, four), None);
+        assert_eq!(super::bisect(|x| x, L32::NAR, four), None);
+        assert_eq!(super::bisect(|_| L32::NAR, two, four), None);
+        let nar_inside = |x: L32| if x == L32::ZERO { L32::NAR } else { x };
+        assert_eq!(
+            super::bisect(nar_inside, L32::from_bits(0x80800000), two),
+            None
+        );
+    }
+}
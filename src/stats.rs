@@ -0,0 +1,375 @@
+//! Statistics over slices of values, and accumulators for streams of values.
+
+use crate::{exception, slice::NarPolicy, L32};
+
+/// Calculates the geometric mean.
+///
+/// The logarithms are summed exactly, averaged and rounded to nearest, ties to even.
+/// Returns ZERO if any value is ZERO, and NaR if the slice is empty or contains a negative
+/// value or NaR.
+pub fn geometric_mean(values: &[L32]) -> L32 {
+    if values.is_empty() {
+        return L32::NAR;
+    }
+
+    let mut sum = 0i128;
+    let mut zero = false;
+    let mut nar = false;
+    for &value in values {
+        sum += i128::from(value.raw_exp());
+        zero |= value == L32::ZERO;
+        nar |= value.to_bits() & 0x80000000 != 0;
+    }
+
+    let (res, inexact) = mean_exp(sum, values.len() as i128, zero, nar);
+    exception::check("geometric_mean", values, None, inexact, res);
+
+    res
+}
+
+/// Streaming version of [`geometric_mean`], using constant memory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GeometricMean {
+    sum: i128,
+    count: u64,
+    zero: bool,
+    negative: bool,
+    nar: bool,
+}
+
+impl GeometricMean {
+    /// Creates an accumulator without any values.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            sum: 0,
+            count: 0,
+            zero: false,
+            negative: false,
+            nar: false,
+        }
+    }
+
+    /// Adds a value.
+    #[inline]
+    pub fn push(&mut self, x: L32) {
+        self.sum += i128::from(x.raw_exp());
+        self.count += 1;
+        self.zero |= x == L32::ZERO;
+        self.negative |= x.sign();
+        self.nar |= x == L32::NAR;
+    }
+
+    /// Returns the number of values added.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the geometric mean of the values added so far.
+    ///
+    /// The result is the same as [`geometric_mean`] of all values.
+    pub fn value(&self) -> L32 {
+        if self.count == 0 {
+            return L32::NAR;
+        }
+
+        let nar = self.negative || self.nar;
+        let (res, inexact) = mean_exp(self.sum, self.count.into(), self.zero, nar);
+        // Only whether any operand was NaR matters for reporting.
+        let operands: &[L32] = if self.nar { &[L32::NAR] } else { &[] };
+        exception::check("geometric_mean", operands, None, inexact, res);
+
+        res
+    }
+}
+
+/// Streaming version of [`slice::min`](crate::slice::min).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RunningMin {
+    nar: NarPolicy,
+    key: Option<u32>,
+}
+
+impl RunningMin {
+    /// Creates an accumulator without any values.
+    #[inline]
+    pub const fn new(nar: NarPolicy) -> Self {
+        Self { nar, key: None }
+    }
+
+    /// Adds a value.
+    #[inline]
+    pub fn push(&mut self, x: L32) {
+        if x == L32::NAR && self.nar == NarPolicy::Skip {
+            return;
+        }
+        // NaR sorts first, so it wins when propagating.
+        let key = x.to_sortable_bits();
+        self.key = Some(self.key.map_or(key, |k| k.min(key)));
+    }
+
+    /// Returns the smallest value added so far.
+    ///
+    /// Returns NaR if no values were added or, when skipping NaR, only NaR was added.
+    #[inline]
+    pub fn value(&self) -> L32 {
+        self.key.map_or(L32::NAR, L32::from_sortable_bits)
+    }
+}
+
+/// Streaming version of [`slice::max`](crate::slice::max).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RunningMax {
+    nar: NarPolicy,
+    key: Option<u32>,
+}
+
+impl RunningMax {
+    /// Creates an accumulator without any values.
+    #[inline]
+    pub const fn new(nar: NarPolicy) -> Self {
+        Self { nar, key: None }
+    }
+
+    /// Adds a value.
+    #[inline]
+    pub fn push(&mut self, x: L32) {
+        let key = match (x == L32::NAR, self.nar) {
+            (true, NarPolicy::Skip) => return,
+            // Sticks, since nothing is larger.
+            (true, NarPolicy::Propagate) => u32::MAX,
+            (false, _) => x.to_sortable_bits() - 1,
+        };
+        self.key = Some(self.key.map_or(key, |k| k.max(key)));
+    }
+
+    /// Returns the largest value added so far.
+    ///
+    /// Returns NaR if no values were added or, when skipping NaR, only NaR was added.
+    #[inline]
+    pub fn value(&self) -> L32 {
+        self.key
+            .map_or(L32::NAR, |k| L32::from_sortable_bits(k.wrapping_add(1)))
+    }
+}
+
+/// Running product of a stream of values.
+///
+/// Like with multiplication, the product is exact and the exponent wraps around if it's out of
+/// range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RunningProduct {
+    product: L32,
+}
+
+impl RunningProduct {
+    /// Creates an accumulator without any values, the product is ONE.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { product: L32::ONE }
+    }
+
+    /// Multiplies the product by a value.
+    #[inline]
+    pub fn push(&mut self, x: L32) {
+        self.product *= x;
+    }
+
+    /// Returns the product of the values added so far.
+    #[inline]
+    pub fn value(&self) -> L32 {
+        self.product
+    }
+}
+
+impl Default for RunningProduct {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Divides a sum of exponents by the number of values, returning the rounded mean and whether
+/// it's inexact.
+fn mean_exp(sum: i128, len: i128, zero: bool, nar: bool) -> (L32, bool) {
+    let mut exp = sum.div_euclid(len);
+    let rem = sum.rem_euclid(len);
+    if 2 * rem > len || (2 * rem == len && exp & 1 != 0) {
+        exp += 1;
+    }
+    // The mean of in range exponents is in range itself.
+    let mut res = L32::from_bits(exp as u32 & 0x7FFFFFFF);
+
+    if zero {
+        res = L32::ZERO;
+    }
+    if nar {
+        res = L32::NAR;
+    }
+
+    (res, rem != 0)
+}
+
+/// Occupancy of the exponents in a set of values, see [`exponent_histogram`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExponentHistogram {
+    /// Number of values per exponent, the bin for exponent `e` is at index `e + 128`.
+    ///
+    /// Each bin covers an octave `[2^e, 2^(e + 1))` of magnitudes.
+    pub bins: [usize; 256],
+    /// Number of values that are ZERO.
+    pub zero: usize,
+    /// Number of values that are NaR.
+    pub nar: usize,
+    /// Number of values that are negative, these are also counted in `bins`.
+    pub negative: usize,
+}
+
+impl ExponentHistogram {
+    /// Returns the count of values with exponent `exp`.
+    #[inline]
+    pub fn bin(&self, exp: i8) -> usize {
+        self.bins[(i32::from(exp) + 128) as usize]
+    }
+
+    /// Returns the smallest and largest occupied exponent, or `None` if all bins are empty.
+    pub fn range(&self) -> Option<(i8, i8)> {
+        let exp = |i: usize| (i as i32 - 128) as i8;
+        let min = self.bins.iter().position(|&n| n != 0)?;
+        let max = self.bins.iter().rposition(|&n| n != 0)?;
+        Some((exp(min), exp(max)))
+    }
+}
+
+/// Counts the values per [`exponent`](L32::exponent) of their magnitude.
+///
+/// ZERO and NaR are counted separately and not in any bin.
+pub fn exponent_histogram(values: &[L32]) -> ExponentHistogram {
+    let mut res = ExponentHistogram {
+        bins: [0; 256],
+        zero: 0,
+        nar: 0,
+        negative: 0,
+    };
+    for &value in values {
+        if value == L32::ZERO {
+            res.zero += 1;
+        } else if value == L32::NAR {
+            res.nar += 1;
+        } else {
+            res.bins[(i32::from(value.exponent()) + 128) as usize] += 1;
+            res.negative += usize::from(value.sign());
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn geometric_mean() {
+        fn test(bits: &[u32], res: u32) {
+            let mut values = [L32::ZERO; 8];
+            for (value, &bits) in values.iter_mut().zip(bits) {
+                *value = L32::from_bits(bits);
+            }
+            let res = L32::from_bits(res);
+            assert_eq!(super::geometric_mean(&values[..bits.len()]), res);
+
+            let mut acc = GeometricMean::new();
+            for &value in &values[..bits.len()] {
+                acc.push(value);
+            }
+            assert_eq!(acc.count(), bits.len() as u64);
+            assert_eq!(acc.value(), res);
+        }
+
+        test(&[], 0xC0000000);
+        test(&[0x00000000], 0x00000000);
+        test(&[0x12345678], 0x12345678);
+        test(&[0x7FFFFFFF], 0x7FFFFFFF);
+        test(&[0x00800000, 0x01800000], 0x01000000);
+        test(&[0x3FFFFFFF, 0x3FFFFFFF, 0x3FFFFFFF], 0x3FFFFFFF);
+        test(&[0x40000001, 0x40000001], 0x40000001);
+        test(&[0x3FFFFFFF, 0x40000001], 0x00000000);
+        // Rounding
+        test(&[0x00000000, 0x00000001], 0x00000000);
+        test(&[0x00000001, 0x00000002], 0x00000002);
+        test(&[0x00000000, 0x00000000, 0x00000002], 0x00000001);
+        test(&[0x00000000, 0x00000000, 0x00000001], 0x00000000);
+        test(&[0x7FFFFFFF, 0x00000000], 0x00000000);
+        test(&[0x7FFFFFFF, 0x7FFFFFFE], 0x7FFFFFFE);
+        // ZERO, negative and NaR
+        test(&[0x00800000, 0x40000000], 0x40000000);
+        test(&[0x00800000, 0x80800000], 0xC0000000);
+        test(&[0x40000000, 0x80800000], 0xC0000000);
+        test(&[0x40000000, 0xC0000000], 0xC0000000);
+    }
+
+    #[test]
+    fn exponent_histogram() {
+        let values = [
+            0x00000000, 0x007FFFFF, 0x80400000, 0x00800000, 0x7FFFFFFF, 0x3FFFFFFF, 0x40000001,
+            0x40000000, 0xC0000000, 0xC0000000,
+        ]
+        .map(L32::from_bits);
+        let hist = super::exponent_histogram(&values);
+
+        assert_eq!(hist.bin(0), 3);
+        assert_eq!(hist.bin(1), 1);
+        assert_eq!(hist.bin(-1), 1);
+        assert_eq!(hist.bin(127), 1);
+        assert_eq!(hist.bin(-128), 1);
+        assert_eq!(hist.bins.iter().sum::<usize>(), 7);
+        assert_eq!((hist.zero, hist.nar, hist.negative), (1, 2, 1));
+        assert_eq!(hist.range(), Some((-128, 127)));
+
+        let hist = super::exponent_histogram(&values[1..3]);
+        assert_eq!(hist.range(), Some((0, 0)));
+        let hist = super::exponent_histogram(&values[7..]);
+        assert_eq!(hist.range(), None);
+        assert_eq!((hist.zero, hist.nar, hist.negative), (1, 2, 0));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "panic-on-nar", ignore = "creates NaR")]
+    fn running() {
+        let lists: [&[u32]; 7] = [
+            &[],
+            &[0xC0000000],
+            &[0x12345678],
+            &[0x3FFFFFFF, 0xBFFFFFFF, 0x40000000],
+            &[0x00800000, 0xC0000000, 0xBFFFFFFF, 0x00000000],
+            &[0xC0000000, 0x7FFFFFFF, 0x40000001, 0x3FFFFFFF],
+            &[0x80800000, 0x80800000, 0x7F800000],
+        ];
+        for bits in lists {
+            let mut values = [L32::ZERO; 8];
+            let values = &mut values[..bits.len()];
+            for (value, &bits) in values.iter_mut().zip(bits) {
+                *value = L32::from_bits(bits);
+            }
+
+            for nar in [NarPolicy::Skip, NarPolicy::Propagate] {
+                let mut min = RunningMin::new(nar);
+                let mut max = RunningMax::new(nar);
+                for &value in values.iter() {
+                    min.push(value);
+                    max.push(value);
+                }
+                assert_eq!(min.value(), crate::slice::min(values, nar));
+                assert_eq!(max.value(), crate::slice::max(values, nar));
+            }
+
+            let mut product = RunningProduct::default();
+            values.iter().for_each(|&value| product.push(value));
+            let expected = values.iter().fold(L32::ONE, |acc, &value| acc * value);
+            assert_eq!(product.value(), expected);
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! Experimental logarithmic numbers with tapered precision.
+
+use crate::L32;
+use core::{fmt, ops::*};
+
+/// An experimental 32 bit logarithmic number with tapered precision.
+///
+/// The most significant bit is the sign. Like in a posit, the remaining 31 bits start with a
+/// variable length regime followed by 2 exponent bits, which together encode the integer part
+/// of the base 2 logarithm of the magnitude. The bits that are left hold its fraction. Values
+/// close to 1.0 get up to 27 fraction bits, 4 more than [`L32`], in exchange for fewer towards
+/// the extremes of the range of about 2^±120. All other bits clear encodes ZERO with the sign
+/// clear and NaR with the sign set.
+///
+/// Unlike L32, multiplication and division have to round because the precision of the result
+/// can be lower than that of the operands. All operations round to nearest, ties to even, and
+/// saturate instead of overflowing. No exceptions are reported, the `panic-on-nar`,
+/// `trace-exceptions` and `counters` features only cover L32.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct T32(u32);
+
+/// Fraction bits of the fixed point logarithms used for computation.
+const FRAC_BITS: u32 = 32;
+
+impl T32 {
+    /// Not a Real (NaR).
+    ///
+    /// Exceptional value for operations where the result cannot be expressed as a real number.
+    pub const NAR: Self = Self(0x80000000);
+
+    /// The value 0.0
+    pub const ZERO: Self = Self(0);
+    /// The value 1.0
+    pub const ONE: Self = Self(0x40000000);
+
+    /// Raw transmutation to u32.
+    #[inline]
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Raw transmutation from u32.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Calculates the square root.
+    ///
+    /// Returns NaR if the input is negative or NaR.
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        // The logarithm has at most 27 fraction bits, halving it is exact.
+        let mut res = Self::from_log(0, self.log() >> 1);
+
+        if self == Self::ZERO {
+            res = Self::ZERO;
+        }
+        if self.0 & 0x80000000 != 0 {
+            res = Self::NAR;
+        }
+
+        res
+    }
+
+    /// Decodes the logarithm of the magnitude as a fixed point number.
+    ///
+    /// The result is meaningless for ZERO and NaR.
+    #[inline]
+    fn log(self) -> i64 {
+        let bits = self.0 << 1;
+        let (run, regime) = if bits & 0x80000000 != 0 {
+            let run = bits.leading_ones();
+            (run, i64::from(run) - 1)
+        } else {
+            let run = bits.leading_zeros();
+            (run, -i64::from(run))
+        };
+        // Skip the run and the bit terminating it, the missing bits at the end are 0.
+        let rest = (u64::from(bits) << (run + 1)) as u32;
+        let exp = i64::from(rest >> 30);
+        let frac = i64::from(rest << 2);
+
+        ((regime * 4 + exp) << FRAC_BITS) | frac
+    }
+
+    /// Encodes a magnitude from its logarithm as a fixed point number.
+    ///
+    /// Rounds to nearest, ties to even, and saturates to the smallest and largest magnitude.
+    #[inline]
+    fn from_log(sign: u32, log: i64) -> Self {
+        let int = log >> FRAC_BITS;
+        let regime = int >> 2;
+        let exp = (int & 3) as u128;
+        let frac = u128::from(log as u32);
+
+        let magnitude = if regime > 30 {
+            0x7FFFFFFF
+        } else if regime < -30 {
+            1
+        } else {
+            // Regime bits, including the one terminating the run.
+            let (regime_bits, regime_len) = if regime >= 0 {
+                (((1u128 << (regime + 1)) - 1) << 1, regime as u32 + 2)
+            } else {
+                (1, (-regime) as u32 + 1)
+            };
+            let bits = (regime_bits << (FRAC_BITS + 2)) | (exp << FRAC_BITS) | frac;
+            let shift = regime_len + FRAC_BITS + 2 - 31;
+
+            let mut magnitude = (bits >> shift) as u32;
+            let rem = bits & ((1 << shift) - 1);
+            let half = 1 << (shift - 1);
+            if rem > half || (rem == half && magnitude & 1 != 0) {
+                magnitude += 1;
+            }
+            // Never round to ZERO or into the sign bit.
+            magnitude.clamp(1, 0x7FFFFFFF)
+        };
+
+        Self(sign | magnitude)
+    }
+}
+
+impl From<L32> for T32 {
+    /// Rounds to the nearest T32, saturating outside of its range.
+    #[inline]
+    fn from(x: L32) -> Self {
+        let sign = x.to_bits() & 0x80000000;
+        let log = i64::from(x.raw_exp()) << (FRAC_BITS - L32::FRACTION_BITS);
+        let mut res = Self::from_log(sign, log);
+
+        if x == L32::ZERO {
+            res = Self::ZERO;
+        }
+        if x == L32::NAR {
+            res = Self::NAR;
+        }
+
+        res
+    }
+}
+
+impl From<T32> for L32 {
+    /// Rounds to the nearest L32, the range of T32 fits in L32.
+    #[inline]
+    fn from(x: T32) -> Self {
+        let shift = FRAC_BITS - L32::FRACTION_BITS;
+        let log = x.log();
+        let mut exp = log >> shift;
+        let rem = log & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        if rem > half || (rem == half && exp & 1 != 0) {
+            exp += 1;
+        }
+        let mut res = L32::from_bits((x.0 & 0x80000000) | (exp as u32 & 0x7FFFFFFF));
+
+        if x == T32::ZERO {
+            res = L32::ZERO;
+        }
+        if x == T32::NAR {
+            res = L32::NAR;
+        }
+
+        res
+    }
+}
+
+impl fmt::Debug for T32 {
+    /// Prints the bits and the decoded logarithm, e.g. `0x4C000000: +2^(1 + 2147483648/2^32)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#010X}: ", self.0)?;
+        if *self == Self::ZERO {
+            return f.write_str("ZERO");
+        }
+        if *self == Self::NAR {
+            return f.write_str("NaR");
+        }
+
+        let sign = if self.0 & 0x80000000 != 0 { '-' } else { '+' };
+        let log = self.log();
+        write!(
+            f,
+            "{sign}2^({} + {}/2^{FRAC_BITS})",
+            log >> FRAC_BITS,
+            log as u32
+        )
+    }
+}
+
+impl Mul<T32> for T32 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: T32) -> Self {
+        let sign = (self.0 ^ rhs.0) & 0x80000000;
+        let mut res = Self::from_log(sign, self.log() + rhs.log());
+
+        if self == Self::ZERO || rhs == Self::ZERO {
+            res = Self::ZERO;
+        }
+        if self == Self::NAR || rhs == Self::NAR {
+            res = Self::NAR;
+        }
+
+        res
+    }
+}
+
+impl MulAssign<T32> for T32 {
+    #[inline]
+    fn mul_assign(&mut self, rhs: T32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<T32> for T32 {
+    type Output = T32;
+
+    #[inline]
+    fn div(self, rhs: T32) -> Self::Output {
+        let sign = (self.0 ^ rhs.0) & 0x80000000;
+        let mut res = Self::from_log(sign, self.log() - rhs.log());
+
+        if self == Self::ZERO {
+            res = Self::ZERO;
+        }
+        if self == Self::NAR || rhs == Self::NAR || rhs == Self::ZERO {
+            res = Self::NAR;
+        }
+
+        res
+    }
+}
+
+impl DivAssign<T32> for T32 {
+    #[inline]
+    fn div_assign(&mut self, rhs: T32) {
+        *self = *self / rhs;
+    }
+}
+
+impl Default for T32 {
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_bits;
+
+    #[test]
+    fn encoding() {
+        fn test(bits: u32, log: i64) {
+            assert_eq!(T32(bits).log(), log);
+            assert_eq!(T32::from_log(bits & 0x80000000, log), T32(bits));
+        }
+
+        // 1, 2, 1/2, -4
+        test(0x40000000, 0);
+        test(0x48000000, 1 << 32);
+        test(0x38000000, -1 << 32);
+        test(0xD0000000, 2 << 32);
+        // Largest and smallest magnitude
+        test(0x7FFFFFFF, 120 << 32);
+        test(0x00000001, -120 << 32);
+        // Fraction bits
+        test(0x40000001, 1 << 5);
+        test(0x4FFFFFFF, (2 << 32) - (1 << 5));
+        test(0x7FFFFFF9, 109 << 32);
+        test(0x7FFFFFFE, 116 << 32);
+    }
+
+    #[test]
+    fn rounding() {
+        fn test(log: i64, bits: u32) {
+            assert_eq!(T32::from_log(0, log), T32(bits));
+        }
+
+        test(1 << 4, 0x40000000);
+        test(3 << 4, 0x40000002);
+        test((1 << 4) + 1, 0x40000001);
+        test(-(1 << 4), 0x40000000);
+        test(-(1 << 4) - 1, 0x3FFFFFFF);
+        // Saturation
+        test(121 << 32, 0x7FFFFFFF);
+        test(1 << 40, 0x7FFFFFFF);
+        test(-121 << 32, 0x00000001);
+        test(-1 << 40, 0x00000001);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut state = 0x2545F4914F6CDD1Du64;
+        for _ in 0..10000 {
+            let x = T32(random_bits(&mut state));
+            if x != T32::ZERO && x != T32::NAR {
+                assert_eq!(T32::from_log(x.0 & 0x80000000, x.log()), x);
+            }
+
+            // L32 values with a logarithm in [-20, 20) fit in T32 exactly.
+            let bits = random_bits(&mut state);
+            let int = bits as i32 % 20;
+            let l = L32::from_bits((bits & 0x807FFFFF) | ((int << 23) as u32 & 0x7FFFFFFF));
+            assert_eq!(L32::from(T32::from(l)), l);
+        }
+
+        assert_eq!(T32::from(L32::ZERO), T32::ZERO);
+        assert_eq!(T32::from(L32::NAR), T32::NAR);
+        assert_eq!(T32::from(L32::ONE), T32::ONE);
+        assert_eq!(L32::from(T32::ZERO), L32::ZERO);
+        assert_eq!(L32::from(T32::NAR), L32::NAR);
+        assert_eq!(T32::from(L32::from_bits(0x3FFFFFFF)), T32(0x7FFFFFFF));
+        assert_eq!(L32::from(T32(0x7FFFFFFF)), L32::from_bits(0x3C000000));
+    }
+
+    #[test]
+    fn debug() {
+        extern crate std;
+        use std::format;
+
+        assert_eq!(format!("{:?}", T32::ONE), "0x40000000: +2^(0 + 0/2^32)");
+        assert_eq!(
+            format!("{:?}", T32(0x4C000000)),
+            "0x4C000000: +2^(1 + 2147483648/2^32)"
+        );
+        assert_eq!(
+            format!("{:?}", T32(0xB8000000)),
+            "0xB8000000: -2^(-1 + 0/2^32)"
+        );
+        assert_eq!(
+            format!("{:?}", T32(0x00000001)),
+            "0x00000001: +2^(-120 + 0/2^32)"
+        );
+        assert_eq!(format!("{:?}", T32::ZERO), "0x00000000: ZERO");
+        assert_eq!(format!("{:?}", T32::NAR), "0x80000000: NaR");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let two = T32(0x48000000);
+        let four = T32(0x50000000);
+        let half = T32(0x38000000);
+        assert_eq!(two * two, four);
+        assert_eq!(four / two, two);
+        assert_eq!(two / four, half);
+        assert_eq!(four.sqrt(), two);
+        assert_eq!(T32(0x40000001).sqrt(), T32(0x40000000));
+        assert_eq!(T32(0x40000003).sqrt(), T32(0x40000002));
+        assert_eq!(T32(0xC0000000) * two, T32(0xC8000000));
+
+        assert_eq!(T32(0x7FFFFFFF) * two, T32(0x7FFFFFFF));
+        assert_eq!(T32(0x00000001) / two, T32(0x00000001));
+        assert_eq!(T32::ZERO * T32(0x7FFFFFFF), T32::ZERO);
+        assert_eq!(T32::ZERO * T32::NAR, T32::NAR);
+        assert_eq!(T32::ZERO / two, T32::ZERO);
+        assert_eq!(two / T32::ZERO, T32::NAR);
+        assert_eq!(T32::NAR.sqrt(), T32::NAR);
+        assert_eq!(T32(0xC0000000).sqrt(), T32::NAR);
+        assert_eq!(T32::ZERO.sqrt(), T32::ZERO);
+
+        for bits in [0x40000001, 0x12345678, 0x7FFFFFFF, 0x00000001, 0xDEADBEEF] {
+            assert_eq!(T32(bits) * T32::ONE, T32(bits));
+            assert_eq!(T32(bits) / T32::ONE, T32(bits));
+        }
+    }
+}
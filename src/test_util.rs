@@ -0,0 +1,11 @@
+//! Helpers shared by the unit tests.
+
+/// Deterministic pseudo-random bit patterns (xorshift64).
+///
+/// `state` must not be 0.
+pub(crate) fn random_bits(state: &mut u64) -> u32 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    (*state >> 32) as u32
+}
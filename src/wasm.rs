@@ -0,0 +1,63 @@
+//! JavaScript bindings, the exported class is `L32`.
+
+use crate::L32;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl L32 {
+    /// Not a Real (NaR).
+    #[wasm_bindgen(js_name = nar)]
+    pub fn js_nar() -> Self {
+        Self::NAR
+    }
+
+    /// The value 0.0
+    #[wasm_bindgen(js_name = zero)]
+    pub fn js_zero() -> Self {
+        Self::ZERO
+    }
+
+    /// The value 1.0
+    #[wasm_bindgen(js_name = one)]
+    pub fn js_one() -> Self {
+        Self::ONE
+    }
+
+    /// Raw transmutation from u32.
+    #[wasm_bindgen(js_name = fromBits)]
+    pub fn js_from_bits(bits: u32) -> Self {
+        Self::from_bits(bits)
+    }
+
+    /// Raw transmutation to u32.
+    #[wasm_bindgen(js_name = toBits)]
+    pub fn js_to_bits(&self) -> u32 {
+        self.to_bits()
+    }
+
+    /// Multiplies by another value.
+    #[wasm_bindgen(js_name = mul)]
+    pub fn js_mul(&self, rhs: &L32) -> Self {
+        *self * *rhs
+    }
+
+    /// Divides by another value.
+    #[wasm_bindgen(js_name = div)]
+    pub fn js_div(&self, rhs: &L32) -> Self {
+        *self / *rhs
+    }
+
+    /// Calculates the square root.
+    ///
+    /// Returns NaR if the input is negative or NaR.
+    #[wasm_bindgen(js_name = sqrt)]
+    pub fn js_sqrt(&self) -> Self {
+        self.sqrt()
+    }
+
+    /// Compares the bits with another value.
+    #[wasm_bindgen(js_name = equals)]
+    pub fn js_equals(&self, rhs: &L32) -> bool {
+        self == rhs
+    }
+}
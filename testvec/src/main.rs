@@ -0,0 +1,363 @@
+//! Generates and verifies test vectors for L32 operations, in the spirit of Berkeley TestFloat.
+//!
+//! ```text
+//! log-num-testvec generate <op> [count] [seed] > vectors.txt
+//! log-num-testvec verify <op> < vectors.txt
+//! ```
+//!
+//! Every line of a vector file holds the operands, the expected result and the expected flags,
+//! separated by spaces. Operands and results are the raw bits as exactly 8 hex digits, the flags
+//! are exactly 2 hex digits. Unary operations have one operand, binary operations two. The flag
+//! bits are those of a [`MathContext`] with the default rounding:
+//!
+//! - `01`: inexact, the result was rounded.
+//! - `02`: underflow, the exact logarithm is below the range and wrapped around.
+//! - `04`: overflow, the exact logarithm is above the range and wrapped around.
+//! - `08`: invalid, the result is NaR but no operand is.
+//!
+//! At most one flag is raised per vector, an overflow that wraps around to NaR is only an
+//! overflow.
+//!
+//! `verify` prints every mismatching or malformed line and exits with status 1 if there were
+//! any.
+
+use log_num::{
+    context::{Flags, MathContext},
+    L32,
+};
+use std::{
+    io::{self, BufRead, BufWriter, Write},
+    process::ExitCode,
+};
+
+const INEXACT: u8 = 0x01;
+const UNDERFLOW: u8 = 0x02;
+const OVERFLOW: u8 = 0x04;
+const INVALID: u8 = 0x08;
+
+#[derive(Clone, Copy)]
+enum Op {
+    Mul,
+    Div,
+    Sqrt,
+    Midpoint,
+}
+
+impl Op {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "mul" => Some(Self::Mul),
+            "div" => Some(Self::Div),
+            "sqrt" => Some(Self::Sqrt),
+            "midpoint" => Some(Self::Midpoint),
+            _ => None,
+        }
+    }
+
+    fn operands(self) -> usize {
+        match self {
+            Self::Sqrt => 1,
+            _ => 2,
+        }
+    }
+
+    /// Computes the result and its flags.
+    fn eval(self, operands: &[L32]) -> (L32, u8) {
+        let mut ctx = MathContext::default();
+        let a = operands[0];
+        let b = operands.get(1).copied().unwrap_or(L32::ONE);
+        let res = match self {
+            Self::Mul => ctx.mul(a, b),
+            Self::Div => ctx.div(a, b),
+            Self::Sqrt => ctx.sqrt(a),
+            Self::Midpoint => ctx.midpoint(a, b),
+        };
+
+        let Flags {
+            overflow,
+            underflow,
+            nar,
+            inexact,
+        } = ctx.take_flags();
+        let flags = [
+            (inexact, INEXACT),
+            (underflow, UNDERFLOW),
+            (overflow, OVERFLOW),
+            (nar, INVALID),
+        ];
+        let flags = flags
+            .iter()
+            .filter(|&&(raised, _)| raised)
+            .fold(0, |flags, &(_, bit)| flags | bit);
+
+        (res, flags)
+    }
+}
+
+/// Deterministic pseudo-random bit patterns (splitmix64), mixed with edge cases.
+struct Values {
+    state: u64,
+}
+
+impl Values {
+    const SPECIAL: [u32; 12] = [
+        0x00000000, 0x80000000, 0x40000000, 0xC0000000, 0x40000001, 0xC0000001, 0x3FFFFFFF,
+        0xBFFFFFFF, 0x7FFFFFFF, 0xFFFFFFFF, 0x00000001, 0x80000001,
+    ];
+
+    fn next(&mut self) -> L32 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        // One in eight values is an edge case.
+        if z & 7 == 0 {
+            L32::from_bits(Self::SPECIAL[(z >> 3) as usize % Self::SPECIAL.len()])
+        } else {
+            L32::from_bits((z >> 32) as u32)
+        }
+    }
+}
+
+fn generate(op: Op, count: u64, seed: u64, out: &mut impl Write) -> io::Result<()> {
+    let mut values = Values { state: seed };
+    let mut operands = [L32::ZERO; 2];
+    for _ in 0..count {
+        let operands = &mut operands[..op.operands()];
+        for x in operands.iter_mut() {
+            *x = values.next();
+        }
+        let (res, flags) = op.eval(operands);
+        for x in operands.iter() {
+            write!(out, "{:08X} ", x.to_bits())?;
+        }
+        writeln!(out, "{:08X} {flags:02X}", res.to_bits())?;
+    }
+    out.flush()
+}
+
+/// Parses a field of exactly `digits` hex digits.
+fn parse_hex(s: &str, digits: usize) -> Option<u32> {
+    if s.len() != digits || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(s, 16).ok()
+}
+
+/// Parses a vector line into its operands, result and flags.
+fn parse_line(line: &str, operands: usize) -> Option<(Vec<L32>, L32, u8)> {
+    let fields: Vec<_> = line.split_whitespace().collect();
+    if fields.len() != operands + 2 {
+        return None;
+    }
+    let bits = |s: &str| parse_hex(s, 8).map(L32::from_bits);
+    let xs = fields[..operands]
+        .iter()
+        .map(|s| bits(s))
+        .collect::<Option<_>>()?;
+    let res = bits(fields[operands])?;
+    let flags = parse_hex(fields[operands + 1], 2)? as u8;
+    Some((xs, res, flags))
+}
+
+/// Number of checked vectors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Summary {
+    total: u64,
+    /// Mismatching and malformed lines.
+    failed: u64,
+    malformed: u64,
+}
+
+/// Checks every vector from `input`, printing the failing lines to `out`.
+fn verify(op: Op, input: impl BufRead, out: &mut impl Write) -> io::Result<Summary> {
+    let mut summary = Summary::default();
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        summary.total += 1;
+        let Some((operands, expected, expected_flags)) = parse_line(&line, op.operands()) else {
+            writeln!(out, "line {}: malformed: {line}", i + 1)?;
+            summary.failed += 1;
+            summary.malformed += 1;
+            continue;
+        };
+        let (res, flags) = op.eval(&operands);
+        if res != expected || flags != expected_flags {
+            writeln!(
+                out,
+                "line {}: {line}: got {:08X} {flags:02X}",
+                i + 1,
+                res.to_bits()
+            )?;
+            summary.failed += 1;
+        }
+    }
+    Ok(summary)
+}
+
+fn usage() -> ExitCode {
+    eprintln!("usage: log-num-testvec generate <op> [count] [seed]");
+    eprintln!("       log-num-testvec verify <op>");
+    eprintln!("ops: mul, div, sqrt, midpoint");
+    ExitCode::from(2)
+}
+
+fn main() -> io::Result<ExitCode> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(op) = args.get(1).and_then(|name| Op::parse(name)) else {
+        return Ok(usage());
+    };
+    let number = |i: usize, default: u64| args.get(i).map_or(Some(default), |s| s.parse().ok());
+
+    match args[0].as_str() {
+        "generate" if args.len() <= 4 => {
+            let (Some(count), Some(seed)) = (number(2, 10000), number(3, 1)) else {
+                return Ok(usage());
+            };
+            let mut out = BufWriter::new(io::stdout().lock());
+            generate(op, count, seed, &mut out)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        "verify" if args.len() == 2 => {
+            let summary = verify(op, io::stdin().lock(), &mut io::stdout().lock())?;
+            eprintln!(
+                "{} vectors, {} failed ({} malformed)",
+                summary.total, summary.failed, summary.malformed
+            );
+            Ok(if summary.failed == 0 {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+        _ => Ok(usage()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPS: [Op; 4] = [Op::Mul, Op::Div, Op::Sqrt, Op::Midpoint];
+
+    fn generated(op: Op, count: u64, seed: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        generate(op, count, seed, &mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn parse() {
+        let one = L32::ONE;
+        let zero = L32::ZERO;
+        assert_eq!(
+            parse_line("00000000 40000000 40000000 00", 2),
+            Some((vec![one, zero], zero, 0))
+        );
+        assert_eq!(
+            parse_line(" 80000000\tC0000000  08 ", 1),
+            Some((vec![L32::from_bits(0x80000000)], L32::NAR, INVALID))
+        );
+        assert_eq!(
+            parse_line("deadbeef 3f6f5f77 01", 1),
+            Some((
+                vec![L32::from_bits(0xDEADBEEF)],
+                L32::from_bits(0x3F6F5F77),
+                1
+            ))
+        );
+
+        let malformed = [
+            "",
+            "00000000 00000000",
+            "00000000 00000000 00",
+            "00000000 00000000 00000000 00 00",
+            "+0000001 00000000 00000000 00",
+            "-0000001 00000000 00000000 00",
+            "0x000001 00000000 00000000 00",
+            "1 00000000 00000000 00",
+            "000000001 00000000 00000000 00",
+            "0000000g 00000000 00000000 00",
+            "00000000 00000000 00000000 0",
+            "00000000 00000000 00000000 +1",
+            "00000000 00000000 00000000 100",
+        ];
+        for line in malformed {
+            assert_eq!(parse_line(line, 2), None, "{line}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for op in OPS {
+            let vectors = generated(op, 1000, 7);
+            let mut out = Vec::new();
+            let summary = verify(op, vectors.as_slice(), &mut out).unwrap();
+            assert_eq!(
+                summary,
+                Summary {
+                    total: 1000,
+                    failed: 0,
+                    malformed: 0
+                }
+            );
+            assert!(out.is_empty());
+        }
+    }
+
+    #[test]
+    fn seeds() {
+        assert_eq!(generated(Op::Mul, 100, 3), generated(Op::Mul, 100, 3));
+        assert_ne!(generated(Op::Mul, 100, 0), generated(Op::Mul, 100, 1));
+        assert_ne!(generated(Op::Mul, 100, 2), generated(Op::Mul, 100, 3));
+    }
+
+    #[test]
+    fn flags() {
+        let flags = |op: Op, operands: &[u32]| {
+            let operands: Vec<_> = operands.iter().map(|&x| L32::from_bits(x)).collect();
+            op.eval(&operands).1
+        };
+        assert_eq!(flags(Op::Mul, &[0x00800000, 0x00800000]), 0);
+        assert_eq!(flags(Op::Sqrt, &[0x00000001]), INEXACT);
+        assert_eq!(flags(Op::Div, &[0x40000001, 0x00800000]), UNDERFLOW);
+        assert_eq!(flags(Op::Mul, &[0x3FFFFFFF, 0x00800000]), OVERFLOW);
+        assert_eq!(flags(Op::Mul, &[0xBFFFFFFF, 0x00000001]), OVERFLOW);
+        assert_eq!(flags(Op::Div, &[0x00800000, 0x40000000]), INVALID);
+        assert_eq!(flags(Op::Midpoint, &[0x00800000, 0x80800000]), INVALID);
+        assert_eq!(flags(Op::Sqrt, &[0xC0000000]), 0);
+    }
+
+    #[test]
+    fn failures() {
+        let input = "\
+00800000 00800000 01000000 00
+00800000 00800000 01000001 00
+00800000 00800000 01000000 01
+
++0800000 00800000 01000000 00
+00800000 00800000
+";
+        let mut out = Vec::new();
+        let summary = verify(Op::Mul, input.as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            summary,
+            Summary {
+                total: 5,
+                failed: 4,
+                malformed: 2
+            }
+        );
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().map(|l| l.split(':').next().unwrap()).collect();
+        assert_eq!(lines, ["line 2", "line 3", "line 5", "line 6"]);
+
+        // A file with only malformed lines fails too.
+        let summary = verify(Op::Sqrt, "nonsense\n".as_bytes(), &mut Vec::new()).unwrap();
+        assert_eq!(summary.failed, 1);
+    }
+}